@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock, PoisonError, RwLock};
+
+use alot::{LotId, Lots};
+
+use crate::Name;
+
+type DeprecationCallback = dyn Fn(&Name, &Name) + Send + Sync;
+
+#[derive(Default)]
+struct Aliases {
+    canonical: HashMap<Name, Name>,
+    on_deprecated: Lots<Arc<DeprecationCallback>>,
+}
+
+/// A handle to a callback registered with [`Name::on_deprecated_alias`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct DeprecationCallbackId(LotId);
+
+/// Set while any alias is registered, allowing lookups to skip the alias
+/// registry entirely when aliases are not in use.
+static HAS_ALIASES: AtomicBool = AtomicBool::new(false);
+
+fn aliases() -> &'static RwLock<Aliases> {
+    static ALIASES: OnceLock<RwLock<Aliases>> = OnceLock::new();
+    ALIASES.get_or_init(RwLock::default)
+}
+
+impl Name {
+    /// Registers `old` as a deprecated alias of `new`.
+    ///
+    /// Once registered, `old` is replaced by `new` when parsing names and when
+    /// looking up components by name, such as with
+    /// [`Style::get_by_name`](crate::Style::get_by_name). The callbacks
+    /// registered with [`Name::on_deprecated_alias`] are invoked each time an
+    /// alias is replaced while parsing a name or by
+    /// [`Style::canonicalize`](crate::Style::canonicalize).
+    ///
+    /// Aliases may be chained: if `new` is later registered as an alias, `old`
+    /// resolves to the final name. Returns false without registering the alias
+    /// if it would cause a name to alias itself.
+    ///
+    /// Styles that contained `old` before the alias was registered can be
+    /// updated using [`Style::canonicalize`](crate::Style::canonicalize).
+    ///
+    /// Aliases are registered for the entire process until removed with
+    /// [`Name::unregister_alias`].
+    ///
+    /// This crate does not implement deserialization for [`Name`]. Aliases are
+    /// resolved by its parsing functions, [`str::parse`] and
+    /// [`Name::from_kebab_case`]. Names produced by other parsers should be
+    /// passed through [`Name::canonical`].
+    pub fn register_alias(old: Name, new: Name) -> bool {
+        let mut aliases = aliases().write().unwrap_or_else(PoisonError::into_inner);
+        let mut target = &new;
+        loop {
+            if target == &old {
+                return false;
+            }
+            match aliases.canonical.get(target) {
+                Some(next) => target = next,
+                None => break,
+            }
+        }
+        aliases.canonical.insert(old, new);
+        HAS_ALIASES.store(true, Ordering::Release);
+        true
+    }
+
+    /// Removes the alias `old` registered with [`Name::register_alias`].
+    /// Returns true if `old` was an alias.
+    ///
+    /// Aliases of `old` are kept and resolve to `old` once it is removed.
+    /// Component types whose [`ComponentId`](crate::ComponentId) was assigned
+    /// while their name was aliased keep the canonical name's id.
+    pub fn unregister_alias(old: &Name) -> bool {
+        let mut aliases = aliases().write().unwrap_or_else(PoisonError::into_inner);
+        let removed = aliases.canonical.remove(old).is_some();
+        HAS_ALIASES.store(!aliases.canonical.is_empty(), Ordering::Release);
+        removed
+    }
+
+    /// Adds a callback invoked with the deprecated and canonical names each
+    /// time an alias registered with [`Name::register_alias`] is replaced
+    /// while parsing a name or by
+    /// [`Style::canonicalize`](crate::Style::canonicalize).
+    ///
+    /// Callbacks are invoked for every alias, including aliases registered by
+    /// other libraries, until removed with
+    /// [`Name::remove_deprecated_alias_callback`].
+    pub fn on_deprecated_alias(
+        callback: impl Fn(&Name, &Name) + Send + Sync + 'static,
+    ) -> DeprecationCallbackId {
+        DeprecationCallbackId(
+            aliases()
+                .write()
+                .unwrap_or_else(PoisonError::into_inner)
+                .on_deprecated
+                .push(Arc::new(callback)),
+        )
+    }
+
+    /// Removes a callback registered with [`Name::on_deprecated_alias`].
+    /// Returns true if the callback was found.
+    pub fn remove_deprecated_alias_callback(id: DeprecationCallbackId) -> bool {
+        aliases()
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .on_deprecated
+            .remove(id.0)
+            .is_some()
+    }
+
+    /// Returns true if this name is a registered alias of another name.
+    #[must_use]
+    pub fn is_alias(&self) -> bool {
+        HAS_ALIASES.load(Ordering::Acquire)
+            && aliases()
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .canonical
+                .contains_key(self)
+    }
+
+    /// Returns the name this name is an alias of, following chained aliases,
+    /// or `self` if this name is not an alias.
+    ///
+    /// This does not invoke the callbacks registered with
+    /// [`Name::on_deprecated_alias`].
+    #[must_use]
+    pub fn canonical(&self) -> Name {
+        if !HAS_ALIASES.load(Ordering::Acquire) {
+            return self.clone();
+        }
+        let aliases = aliases().read().unwrap_or_else(PoisonError::into_inner);
+        let Some(mut canonical) = aliases.canonical.get(self) else {
+            return self.clone();
+        };
+        while let Some(next) = aliases.canonical.get(canonical) {
+            canonical = next;
+        }
+        canonical.clone()
+    }
+
+    /// Returns the [canonical](Self::canonical) form of this name, reusing
+    /// `self` when no aliases are registered.
+    pub(crate) fn into_canonical(self) -> Name {
+        if HAS_ALIASES.load(Ordering::Acquire) {
+            self.canonical()
+        } else {
+            self
+        }
+    }
+
+    /// Returns the [canonical](Self::canonical) form of this name, reporting
+    /// the replacement to the deprecation callbacks if this name is an alias.
+    pub(crate) fn resolve_alias(self) -> Name {
+        let canonical = self.canonical();
+        if canonical != self {
+            report_deprecated_alias(&self, &canonical);
+        }
+        canonical
+    }
+}
+
+/// Invokes each callback registered with [`Name::on_deprecated_alias`].
+pub(crate) fn report_deprecated_alias(alias: &Name, canonical: &Name) {
+    let callbacks = aliases()
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .on_deprecated
+        .iter()
+        .cloned()
+        .collect::<Vec<_>>();
+    for callback in callbacks {
+        callback(alias, canonical);
+    }
+}
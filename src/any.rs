@@ -3,9 +3,10 @@ use std::option::Option;
 use std::panic::{RefUnwindSafe, UnwindSafe};
 
 use crate::components::DynamicComponent;
-use crate::{Name, StyleComponent};
+use crate::world::{AnyColumn, Column};
+use crate::{ComponentId, InvalidValue, Name, ResolutionContext};
 
-/// A [`DynamicComponent`]/[`StyleComponent`] that can be boxed for storage and
+/// A [`DynamicComponent`]/[`StyleComponent`](crate::StyleComponent) that can be boxed for storage and
 /// cloned.
 #[allow(clippy::module_name_repetitions)]
 pub(crate) trait AnyStyleComponent:
@@ -24,11 +25,25 @@ pub(crate) trait AnyStyleComponent:
 
     fn merge_with(&mut self, other: &dyn AnyStyleComponent);
 
+    fn resolve_against_parent(&mut self, parent: &dyn AnyStyleComponent);
+
     fn inherited(&self) -> bool;
 
+    fn resolve_units(&mut self, context: &ResolutionContext);
+
+    fn normalize(&mut self);
+
+    fn validate(&self) -> Result<(), InvalidValue>;
+
+    fn equivalent(&self, other: &dyn AnyStyleComponent) -> bool;
+
     fn debug(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
 
     fn name(&self) -> Name;
+
+    fn component_id(&self) -> ComponentId;
+
+    fn new_column(&self) -> Box<dyn AnyColumn>;
 }
 
 impl<T> AnyStyleComponent for Option<T>
@@ -48,26 +63,51 @@ where
     }
 
     fn merge_with(&mut self, other: &dyn AnyStyleComponent) {
-        let myself = self
-            .as_mut_any()
-            .downcast_mut::<Self>()
-            .expect("incorrect type")
-            .as_mut()
-            .expect("style unboxed");
-        let other = other
-            .as_any()
-            .downcast_ref::<Self>()
-            .expect("incorrect type")
-            .as_ref()
-            .expect("style unboxed");
+        // Components of different types can share a name, e.g. a typed
+        // component and a `DynamicValue`. These can't be merged, so the
+        // existing value is kept.
+        let Some(other) = other.as_any().downcast_ref::<Self>() else {
+            return;
+        };
+        let myself = self.as_mut().expect("style unboxed");
+        myself.merge(other.as_ref().expect("style unboxed"));
+    }
 
-        myself.merge(other);
+    fn resolve_against_parent(&mut self, parent: &dyn AnyStyleComponent) {
+        let Some(parent) = parent.as_any().downcast_ref::<Self>() else {
+            return;
+        };
+        let myself = self.as_mut().expect("style unboxed");
+        myself.resolve_against_parent(parent.as_ref().expect("style unboxed"));
     }
 
     fn inherited(&self) -> bool {
         self.as_ref().expect("style unboxed").inherited()
     }
 
+    fn resolve_units(&mut self, context: &ResolutionContext) {
+        self.as_mut()
+            .expect("style unboxed")
+            .resolve_units(context);
+    }
+
+    fn normalize(&mut self) {
+        self.as_mut().expect("style unboxed").normalize();
+    }
+
+    fn validate(&self) -> Result<(), InvalidValue> {
+        self.as_ref().expect("style unboxed").validate()
+    }
+
+    fn equivalent(&self, other: &dyn AnyStyleComponent) -> bool {
+        let myself = self.as_ref().expect("style unboxed");
+        other
+            .as_any()
+            .downcast_ref::<Self>()
+            .and_then(Option::as_ref)
+            .map_or(false, |other| myself.equivalent(other))
+    }
+
     fn debug(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.as_ref().expect("style unboxed").fmt(f)
     }
@@ -75,9 +115,17 @@ where
     fn name(&self) -> Name {
         self.as_ref().expect("style unboxed").name()
     }
+
+    fn component_id(&self) -> ComponentId {
+        self.as_ref().expect("style unboxed").component_id()
+    }
+
+    fn new_column(&self) -> Box<dyn AnyColumn> {
+        Box::<Column<T>>::default()
+    }
 }
 
-/// A boxed [`StyleComponent`].
+/// A boxed [`StyleComponent`](crate::StyleComponent).
 pub struct AnyComponent(Box<dyn AnyStyleComponent>);
 
 impl AnyComponent {
@@ -89,7 +137,7 @@ impl AnyComponent {
     /// Returns the contained style component. Returns `None` if `T` is not the
     /// same type that was wrapped.
     #[must_use]
-    pub fn get<T: StyleComponent>(&self) -> Option<&T> {
+    pub fn get<T: DynamicComponent>(&self) -> Option<&T> {
         self.0
             .as_any()
             .downcast_ref::<Option<T>>()
@@ -99,35 +147,96 @@ impl AnyComponent {
     /// Returns the contained style component. Returns `None` if `T` is not the
     /// same type that was wrapped.
     #[must_use]
-    pub fn get_mut<T: StyleComponent>(&mut self) -> Option<&mut T> {
+    pub fn get_mut<T: DynamicComponent>(&mut self) -> Option<&mut T> {
         self.0
             .as_mut_any()
             .downcast_mut::<Option<T>>()
             .and_then(Option::as_mut)
     }
 
+    /// Renames a contained [`DynamicValue`](crate::DynamicValue) whose name is
+    /// a deprecated [alias](Name::register_alias) to its canonical name.
+    /// Returns the previous name if the value was renamed.
+    pub(crate) fn canonicalize_name(&mut self) -> Option<Name> {
+        let dynamic = self.get_mut::<crate::DynamicValue>()?;
+        let canonical = dynamic.name.canonical();
+        (canonical != dynamic.name).then(|| std::mem::replace(&mut dynamic.name, canonical))
+    }
+
+    /// Returns the contained style component, consuming `self`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(self)` if `T` is not the same type that was wrapped.
+    pub fn into_inner<T: DynamicComponent>(mut self) -> Result<T, Self> {
+        if let Some(component) = self
+            .0
+            .as_mut_any()
+            .downcast_mut::<Option<T>>()
+            .and_then(Option::take)
+        {
+            Ok(component)
+        } else {
+            Err(self)
+        }
+    }
+
     /// Returns the result of [`DynamicComponent::inherited`].
     #[must_use]
     pub fn inherited(&self) -> bool {
         self.0.inherited()
     }
 
-    /// Calls [`DynamicComponent::merge`] to merge `self` with `other`.
+    /// Calls [`DynamicComponent::resolve_units`] to convert relative units
+    /// into absolute units.
+    pub fn resolve_units(&mut self, context: &ResolutionContext) {
+        self.0.resolve_units(context);
+    }
+
+    /// Calls [`DynamicComponent::normalize`] to adjust the component into a
+    /// canonical form.
+    pub fn normalize(&mut self) {
+        self.0.normalize();
+    }
+
+    /// Calls [`DynamicComponent::validate`] to check whether the component is
+    /// valid.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// This function panics if `other` does not wrap the same type as `self` is
-    /// wrapping.
+    /// Returns an [`InvalidValue`] describing why the component is invalid.
+    pub fn validate(&self) -> Result<(), InvalidValue> {
+        self.0.validate()
+    }
+
+    /// Returns true if `other` wraps the same type as `self` and
+    /// [`DynamicComponent::equivalent`] returns true.
+    #[must_use]
+    pub fn equivalent(&self, other: &Self) -> bool {
+        self.0.equivalent(other.0.as_ref())
+    }
+
+    /// Calls [`DynamicComponent::merge`] to merge `self` with `other`.
+    ///
+    /// If `other` does not wrap the same type as `self`, `self` is left
+    /// unchanged.
     pub fn merge_with(&mut self, other: &Self) {
         self.0.merge_with(other.0.as_ref());
     }
 
-    /// Calls [`DynamicComponent::merge`] and returns the updated value.
+    /// Calls [`DynamicComponent::resolve_against_parent`] to resolve `self`
+    /// relative to `parent`.
     ///
-    /// # Panics
+    /// If `parent` does not wrap the same type as `self`, `self` is left
+    /// unchanged.
+    pub fn resolve_against_parent(&mut self, parent: &Self) {
+        self.0.resolve_against_parent(parent.0.as_ref());
+    }
+
+    /// Calls [`DynamicComponent::merge`] and returns the updated value.
     ///
-    /// This function panics if `other` does not wrap the same type as `self` is
-    /// wrapping.
+    /// If `other` does not wrap the same type as `self`, `self` is returned
+    /// unchanged.
     #[must_use]
     pub fn merged_with(mut self, other: &Self) -> Self {
         self.merge_with(other);
@@ -139,6 +248,17 @@ impl AnyComponent {
     pub fn name(&self) -> Name {
         self.0.name()
     }
+
+    /// Returns the [`ComponentId`] of the component.
+    #[must_use]
+    pub fn component_id(&self) -> ComponentId {
+        self.0.component_id()
+    }
+
+    /// Returns an empty column that can store this component's type.
+    pub(crate) fn new_column(&self) -> Box<dyn AnyColumn> {
+        self.0.new_column()
+    }
 }
 
 impl Clone for AnyComponent {
@@ -0,0 +1,822 @@
+//! Color types and style components.
+//!
+//! [`Color`] is stored in the sRGB color space and can be converted to
+//! [`LinearRgb`] and [`Oklch`]. Colors can be parsed from strings using CSS
+//! syntax:
+//!
+//! ```rust
+//! use stylecs::color::Color;
+//!
+//! let red: Color = "#f00".parse().unwrap();
+//! assert_eq!(red, Color::rgb8(255, 0, 0));
+//! assert_eq!("rgb(255 0 0)".parse::<Color>().unwrap(), red);
+//! assert_eq!("hsl(0, 100%, 50%)".parse::<Color>().unwrap(), red);
+//! assert_eq!("Red".parse::<Color>().unwrap(), red);
+//! ```
+
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::{static_name, Name, StaticName, StyleComponent, AUTHORITY};
+
+/// The color of text, inherited by default.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextColor(pub Color);
+
+impl StyleComponent for TextColor {
+    fn name() -> Name {
+        static NAME: StaticName = static_name!(AUTHORITY, "text_color");
+        NAME.to_name()
+    }
+
+    fn inherited() -> bool {
+        true
+    }
+
+    fn equivalent(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+/// The color used to fill a widget's background.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BackgroundColor(pub Color);
+
+impl StyleComponent for BackgroundColor {
+    fn name() -> Name {
+        static NAME: StaticName = static_name!(AUTHORITY, "background_color");
+        NAME.to_name()
+    }
+
+    fn equivalent(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+/// A color in the sRGB color space with an alpha channel.
+///
+/// Each channel is a value between `0.0` and `1.0`. The color channels are not
+/// premultiplied by `alpha`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color {
+    /// The red channel.
+    pub red: f32,
+    /// The green channel.
+    pub green: f32,
+    /// The blue channel.
+    pub blue: f32,
+    /// The opacity of the color.
+    pub alpha: f32,
+}
+
+impl Color {
+    /// A fully transparent color.
+    pub const TRANSPARENT: Self = Self::rgba(0., 0., 0., 0.);
+    /// Opaque black.
+    pub const BLACK: Self = Self::rgb(0., 0., 0.);
+    /// Opaque white.
+    pub const WHITE: Self = Self::rgb(1., 1., 1.);
+
+    /// Returns an opaque color from the given channels.
+    #[must_use]
+    pub const fn rgb(red: f32, green: f32, blue: f32) -> Self {
+        Self::rgba(red, green, blue, 1.)
+    }
+
+    /// Returns a color from the given channels.
+    #[must_use]
+    pub const fn rgba(red: f32, green: f32, blue: f32, alpha: f32) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+
+    /// Returns an opaque color from 8-bit channels.
+    #[must_use]
+    pub fn rgb8(red: u8, green: u8, blue: u8) -> Self {
+        Self::rgba8(red, green, blue, 255)
+    }
+
+    /// Returns a color from 8-bit channels.
+    #[must_use]
+    pub fn rgba8(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+        Self::rgba(
+            f32::from(red) / 255.,
+            f32::from(green) / 255.,
+            f32::from(blue) / 255.,
+            f32::from(alpha) / 255.,
+        )
+    }
+
+    /// Returns a color from hue (in degrees), saturation, and lightness
+    /// values. Saturation and lightness are values between `0.0` and `1.0`.
+    #[must_use]
+    pub fn hsla(hue: f32, saturation: f32, lightness: f32, alpha: f32) -> Self {
+        let hue = hue.rem_euclid(360.);
+        let saturation = saturation.clamp(0., 1.);
+        let lightness = lightness.clamp(0., 1.);
+        let channel = |n: f32| {
+            let k = (n + hue / 30.) % 12.;
+            let a = saturation * lightness.min(1. - lightness);
+            lightness - a * (k - 3.).min(9. - k).clamp(-1., 1.)
+        };
+        Self::rgba(channel(0.), channel(8.), channel(4.), alpha)
+    }
+
+    /// Returns this color with its alpha channel replaced by `alpha`.
+    #[must_use]
+    pub const fn with_alpha(mut self, alpha: f32) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    /// Returns the channels of this color converted to 8-bit values.
+    #[must_use]
+    pub fn to_rgba8(self) -> [u8; 4] {
+        [self.red, self.green, self.blue, self.alpha].map(|channel| {
+            // The value is clamped to the range of u8.
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let channel = (channel.clamp(0., 1.) * 255.).round() as u8;
+            channel
+        })
+    }
+
+    /// Returns this color converted to linear RGB.
+    #[must_use]
+    pub fn to_linear(self) -> LinearRgb {
+        LinearRgb {
+            red: srgb_to_linear(self.red),
+            green: srgb_to_linear(self.green),
+            blue: srgb_to_linear(self.blue),
+            alpha: self.alpha,
+        }
+    }
+
+    /// Returns this color converted to OKLCH.
+    #[must_use]
+    pub fn to_oklch(self) -> Oklch {
+        self.to_linear().to_oklch()
+    }
+
+    /// Returns the result of compositing `self` over `background` using
+    /// source-over alpha compositing in the sRGB color space.
+    #[must_use]
+    pub fn over(self, background: Self) -> Self {
+        let alpha = self.alpha + background.alpha * (1. - self.alpha);
+        if alpha <= 0. {
+            return Self::TRANSPARENT;
+        }
+        let channel = |foreground: f32, background_channel: f32| {
+            (foreground * self.alpha + background_channel * background.alpha * (1. - self.alpha))
+                / alpha
+        };
+        Self::rgba(
+            channel(self.red, background.red),
+            channel(self.green, background.green),
+            channel(self.blue, background.blue),
+            alpha,
+        )
+    }
+
+    /// Returns the color `fraction` of the way between `self` and `other`,
+    /// interpolated in `space`.
+    ///
+    /// `fraction` is clamped between `0.0` and `1.0`. Colors are interpolated
+    /// using premultiplied alpha. When interpolating in [`ColorSpace::Oklch`],
+    /// hues are interpolated along the shorter arc.
+    #[must_use]
+    pub fn interpolate(self, other: Self, fraction: f32, space: ColorSpace) -> Self {
+        let fraction = fraction.clamp(0., 1.);
+        match space {
+            ColorSpace::Srgb => {
+                let [red, green, blue, alpha] = interpolate_premultiplied(
+                    [self.red, self.green, self.blue, self.alpha],
+                    [other.red, other.green, other.blue, other.alpha],
+                    fraction,
+                );
+                Self::rgba(red, green, blue, alpha)
+            }
+            ColorSpace::LinearRgb => {
+                let a = self.to_linear();
+                let b = other.to_linear();
+                let [red, green, blue, alpha] = interpolate_premultiplied(
+                    [a.red, a.green, a.blue, a.alpha],
+                    [b.red, b.green, b.blue, b.alpha],
+                    fraction,
+                );
+                LinearRgb {
+                    red,
+                    green,
+                    blue,
+                    alpha,
+                }
+                .to_srgb()
+            }
+            ColorSpace::Oklch => {
+                let a = self.to_oklch();
+                let b = other.to_oklch();
+                let mut a_hue = a.hue;
+                let mut b_hue = b.hue;
+                if a.chroma <= ACHROMATIC_CHROMA {
+                    a_hue = b_hue;
+                } else if b.chroma <= ACHROMATIC_CHROMA {
+                    b_hue = a_hue;
+                }
+                let mut delta = (b_hue - a_hue).rem_euclid(360.);
+                if delta > 180. {
+                    delta -= 360.;
+                }
+                let [lightness, chroma, _, alpha] = interpolate_premultiplied(
+                    [a.lightness, a.chroma, 0., a.alpha],
+                    [b.lightness, b.chroma, 0., b.alpha],
+                    fraction,
+                );
+                Oklch {
+                    lightness,
+                    chroma,
+                    hue: (a_hue + delta * fraction).rem_euclid(360.),
+                    alpha,
+                }
+                .to_srgb()
+            }
+        }
+    }
+}
+
+impl Default for Color {
+    fn default() -> Self {
+        Self::TRANSPARENT
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [red, green, blue, alpha] = self.to_rgba8();
+        write!(f, "#{red:02x}{green:02x}{blue:02x}")?;
+        if alpha < 255 {
+            write!(f, "{alpha:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(hex) = s.strip_prefix('#') {
+            return parse_hex(hex);
+        }
+
+        let lowercase = s.to_ascii_lowercase();
+        if let Some((function, arguments)) = lowercase.split_once('(') {
+            let arguments = arguments
+                .strip_suffix(')')
+                .ok_or(ParseColorError::InvalidSyntax)?;
+            let arguments = arguments
+                .split(|ch: char| ch == ',' || ch == '/' || ch.is_ascii_whitespace())
+                .filter(|argument| !argument.is_empty())
+                .collect::<Vec<_>>();
+            let (channels, alpha) = match arguments.as_slice() {
+                [a, b, c] => ([*a, *b, *c], None),
+                [a, b, c, alpha] => ([*a, *b, *c], Some(*alpha)),
+                _ => return Err(ParseColorError::InvalidSyntax),
+            };
+            let alpha = alpha.map_or(Ok(1.), parse_alpha)?;
+            match function.trim() {
+                "rgb" | "rgba" => {
+                    let [red, green, blue] = channels;
+                    Ok(Self::rgba(
+                        parse_rgb_channel(red)?,
+                        parse_rgb_channel(green)?,
+                        parse_rgb_channel(blue)?,
+                        alpha,
+                    ))
+                }
+                "hsl" | "hsla" => {
+                    let [hue, saturation, lightness] = channels;
+                    Ok(Self::hsla(
+                        parse_hue(hue)?,
+                        parse_percentage(saturation)?,
+                        parse_percentage(lightness)?,
+                        alpha,
+                    ))
+                }
+                _ => Err(ParseColorError::InvalidSyntax),
+            }
+        } else if lowercase == "transparent" {
+            Ok(Self::TRANSPARENT)
+        } else {
+            NAMED_COLORS
+                .binary_search_by(|(name, _)| name.cmp(&lowercase.as_str()))
+                .map(|index| {
+                    let [red, green, blue] = NAMED_COLORS[index].1;
+                    Self::rgb8(red, green, blue)
+                })
+                .map_err(|_| ParseColorError::UnknownColor)
+        }
+    }
+}
+
+fn parse_hex(hex: &str) -> Result<Color, ParseColorError> {
+    if !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err(ParseColorError::InvalidHex);
+    }
+    let digit = |index: usize| {
+        u8::from_str_radix(&hex[index..=index], 16).map_err(|_| ParseColorError::InvalidHex)
+    };
+    let pair = |index: usize| {
+        u8::from_str_radix(&hex[index..index + 2], 16).map_err(|_| ParseColorError::InvalidHex)
+    };
+    match hex.len() {
+        3 | 4 => {
+            let alpha = if hex.len() == 4 { digit(3)? * 17 } else { 255 };
+            Ok(Color::rgba8(
+                digit(0)? * 17,
+                digit(1)? * 17,
+                digit(2)? * 17,
+                alpha,
+            ))
+        }
+        6 | 8 => {
+            let alpha = if hex.len() == 8 { pair(6)? } else { 255 };
+            Ok(Color::rgba8(pair(0)?, pair(2)?, pair(4)?, alpha))
+        }
+        _ => Err(ParseColorError::InvalidHex),
+    }
+}
+
+fn parse_number(value: &str) -> Result<f32, ParseColorError> {
+    value
+        .parse::<f32>()
+        .ok()
+        .filter(|value| value.is_finite())
+        .ok_or(ParseColorError::InvalidSyntax)
+}
+
+fn parse_rgb_channel(value: &str) -> Result<f32, ParseColorError> {
+    if let Some(percent) = value.strip_suffix('%') {
+        Ok((parse_number(percent)? / 100.).clamp(0., 1.))
+    } else {
+        Ok((parse_number(value)? / 255.).clamp(0., 1.))
+    }
+}
+
+fn parse_percentage(value: &str) -> Result<f32, ParseColorError> {
+    let value = value.strip_suffix('%').unwrap_or(value);
+    Ok((parse_number(value)? / 100.).clamp(0., 1.))
+}
+
+fn parse_alpha(value: &str) -> Result<f32, ParseColorError> {
+    if let Some(percent) = value.strip_suffix('%') {
+        Ok((parse_number(percent)? / 100.).clamp(0., 1.))
+    } else {
+        Ok(parse_number(value)?.clamp(0., 1.))
+    }
+}
+
+fn parse_hue(value: &str) -> Result<f32, ParseColorError> {
+    parse_number(value.strip_suffix("deg").unwrap_or(value))
+}
+
+/// Interpolates two colors using premultiplied alpha. The last element of
+/// each array is the alpha channel.
+fn interpolate_premultiplied(a: [f32; 4], b: [f32; 4], fraction: f32) -> [f32; 4] {
+    let lerp = |a: f32, b: f32| a + (b - a) * fraction;
+    let alpha = lerp(a[3], b[3]);
+    let mut result = [0., 0., 0., alpha];
+    if alpha > 0. {
+        for channel in 0..3 {
+            result[channel] = lerp(a[channel] * a[3], b[channel] * b[3]) / alpha;
+        }
+    }
+    result
+}
+
+/// Chroma values at or below this threshold are considered to have no hue.
+const ACHROMATIC_CHROMA: f32 = 0.000_1;
+
+fn srgb_to_linear(channel: f32) -> f32 {
+    if channel <= 0.040_45 {
+        channel / 12.92
+    } else {
+        ((channel + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(channel: f32) -> f32 {
+    if channel <= 0.003_130_8 {
+        channel * 12.92
+    } else {
+        1.055 * channel.powf(1. / 2.4) - 0.055
+    }
+}
+
+/// A color in the linear RGB color space with an alpha channel.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct LinearRgb {
+    /// The red channel.
+    pub red: f32,
+    /// The green channel.
+    pub green: f32,
+    /// The blue channel.
+    pub blue: f32,
+    /// The opacity of the color.
+    pub alpha: f32,
+}
+
+impl LinearRgb {
+    /// Returns this color converted to sRGB.
+    #[must_use]
+    pub fn to_srgb(self) -> Color {
+        Color::rgba(
+            linear_to_srgb(self.red),
+            linear_to_srgb(self.green),
+            linear_to_srgb(self.blue),
+            self.alpha,
+        )
+    }
+
+    /// Returns this color converted to OKLCH.
+    #[must_use]
+    pub fn to_oklch(self) -> Oklch {
+        let [red, green, blue] = [self.red, self.green, self.blue].map(f64::from);
+        let long =
+            (0.412_221_470_8 * red + 0.536_332_536_3 * green + 0.051_445_992_9 * blue).cbrt();
+        let medium =
+            (0.211_903_498_2 * red + 0.680_699_545_1 * green + 0.107_396_956_6 * blue).cbrt();
+        let short =
+            (0.088_302_461_9 * red + 0.281_718_837_6 * green + 0.629_978_700_5 * blue).cbrt();
+
+        let lightness = 0.210_454_255_3 * long + 0.793_617_785_0 * medium - 0.004_072_046_8 * short;
+        let green_red = 1.977_998_495_1 * long - 2.428_592_205_0 * medium + 0.450_593_709_9 * short;
+        let blue_yellow =
+            0.025_904_037_1 * long + 0.782_771_766_2 * medium - 0.808_675_766_0 * short;
+
+        Oklch {
+            lightness: to_f32(lightness),
+            chroma: to_f32(green_red.hypot(blue_yellow)),
+            hue: to_f32(blue_yellow.atan2(green_red).to_degrees().rem_euclid(360.)),
+            alpha: self.alpha,
+        }
+    }
+}
+
+/// A color in the OKLCH color space with an alpha channel.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct Oklch {
+    /// The perceived lightness, typically between `0.0` and `1.0`.
+    pub lightness: f32,
+    /// The chroma, or colorfulness, of the color. Typically between `0.0` and
+    /// `0.4`.
+    pub chroma: f32,
+    /// The hue angle, in degrees.
+    pub hue: f32,
+    /// The opacity of the color.
+    pub alpha: f32,
+}
+
+impl Oklch {
+    /// Returns this color converted to linear RGB.
+    #[must_use]
+    pub fn to_linear(self) -> LinearRgb {
+        let lightness = f64::from(self.lightness);
+        let chroma = f64::from(self.chroma);
+        let hue = f64::from(self.hue).to_radians();
+        let green_red = chroma * hue.cos();
+        let blue_yellow = chroma * hue.sin();
+
+        let long =
+            (lightness + 0.396_337_777_4 * green_red + 0.215_803_757_3 * blue_yellow).powi(3);
+        let medium =
+            (lightness - 0.105_561_345_8 * green_red - 0.063_854_172_8 * blue_yellow).powi(3);
+        let short =
+            (lightness - 0.089_484_177_5 * green_red - 1.291_485_548_0 * blue_yellow).powi(3);
+
+        LinearRgb {
+            red: to_f32(
+                4.076_741_662_1 * long - 3.307_711_591_3 * medium + 0.230_969_929_2 * short,
+            ),
+            green: to_f32(
+                -1.268_438_004_6 * long + 2.609_757_401_1 * medium - 0.341_319_396_5 * short,
+            ),
+            blue: to_f32(
+                -0.004_196_086_3 * long - 0.703_418_614_7 * medium + 1.707_614_701_0 * short,
+            ),
+            alpha: self.alpha,
+        }
+    }
+
+    /// Returns this color converted to sRGB.
+    #[must_use]
+    pub fn to_srgb(self) -> Color {
+        self.to_linear().to_srgb()
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn to_f32(value: f64) -> f32 {
+    value as f32
+}
+
+/// A color space used for interpolating colors.
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ColorSpace {
+    /// The gamma-encoded sRGB color space.
+    Srgb,
+    /// The linear RGB color space.
+    LinearRgb,
+    /// The perceptually uniform OKLCH color space.
+    #[default]
+    Oklch,
+}
+
+/// An error parsing a [`Color`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ParseColorError {
+    /// A hexadecimal color did not contain 3, 4, 6, or 8 hexadecimal digits.
+    InvalidHex,
+    /// The color name is not a known named color.
+    UnknownColor,
+    /// A color function was malformed.
+    InvalidSyntax,
+}
+
+impl Display for ParseColorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ParseColorError::InvalidHex => "invalid hexadecimal color",
+            ParseColorError::UnknownColor => "unknown color name",
+            ParseColorError::InvalidSyntax => "invalid color syntax",
+        })
+    }
+}
+
+impl Error for ParseColorError {}
+
+/// The named colors defined by CSS, sorted by name.
+static NAMED_COLORS: &[(&str, [u8; 3])] = &[
+    ("aliceblue", [0xf0, 0xf8, 0xff]),
+    ("antiquewhite", [0xfa, 0xeb, 0xd7]),
+    ("aqua", [0x00, 0xff, 0xff]),
+    ("aquamarine", [0x7f, 0xff, 0xd4]),
+    ("azure", [0xf0, 0xff, 0xff]),
+    ("beige", [0xf5, 0xf5, 0xdc]),
+    ("bisque", [0xff, 0xe4, 0xc4]),
+    ("black", [0x00, 0x00, 0x00]),
+    ("blanchedalmond", [0xff, 0xeb, 0xcd]),
+    ("blue", [0x00, 0x00, 0xff]),
+    ("blueviolet", [0x8a, 0x2b, 0xe2]),
+    ("brown", [0xa5, 0x2a, 0x2a]),
+    ("burlywood", [0xde, 0xb8, 0x87]),
+    ("cadetblue", [0x5f, 0x9e, 0xa0]),
+    ("chartreuse", [0x7f, 0xff, 0x00]),
+    ("chocolate", [0xd2, 0x69, 0x1e]),
+    ("coral", [0xff, 0x7f, 0x50]),
+    ("cornflowerblue", [0x64, 0x95, 0xed]),
+    ("cornsilk", [0xff, 0xf8, 0xdc]),
+    ("crimson", [0xdc, 0x14, 0x3c]),
+    ("cyan", [0x00, 0xff, 0xff]),
+    ("darkblue", [0x00, 0x00, 0x8b]),
+    ("darkcyan", [0x00, 0x8b, 0x8b]),
+    ("darkgoldenrod", [0xb8, 0x86, 0x0b]),
+    ("darkgray", [0xa9, 0xa9, 0xa9]),
+    ("darkgreen", [0x00, 0x64, 0x00]),
+    ("darkgrey", [0xa9, 0xa9, 0xa9]),
+    ("darkkhaki", [0xbd, 0xb7, 0x6b]),
+    ("darkmagenta", [0x8b, 0x00, 0x8b]),
+    ("darkolivegreen", [0x55, 0x6b, 0x2f]),
+    ("darkorange", [0xff, 0x8c, 0x00]),
+    ("darkorchid", [0x99, 0x32, 0xcc]),
+    ("darkred", [0x8b, 0x00, 0x00]),
+    ("darksalmon", [0xe9, 0x96, 0x7a]),
+    ("darkseagreen", [0x8f, 0xbc, 0x8f]),
+    ("darkslateblue", [0x48, 0x3d, 0x8b]),
+    ("darkslategray", [0x2f, 0x4f, 0x4f]),
+    ("darkslategrey", [0x2f, 0x4f, 0x4f]),
+    ("darkturquoise", [0x00, 0xce, 0xd1]),
+    ("darkviolet", [0x94, 0x00, 0xd3]),
+    ("deeppink", [0xff, 0x14, 0x93]),
+    ("deepskyblue", [0x00, 0xbf, 0xff]),
+    ("dimgray", [0x69, 0x69, 0x69]),
+    ("dimgrey", [0x69, 0x69, 0x69]),
+    ("dodgerblue", [0x1e, 0x90, 0xff]),
+    ("firebrick", [0xb2, 0x22, 0x22]),
+    ("floralwhite", [0xff, 0xfa, 0xf0]),
+    ("forestgreen", [0x22, 0x8b, 0x22]),
+    ("fuchsia", [0xff, 0x00, 0xff]),
+    ("gainsboro", [0xdc, 0xdc, 0xdc]),
+    ("ghostwhite", [0xf8, 0xf8, 0xff]),
+    ("gold", [0xff, 0xd7, 0x00]),
+    ("goldenrod", [0xda, 0xa5, 0x20]),
+    ("gray", [0x80, 0x80, 0x80]),
+    ("green", [0x00, 0x80, 0x00]),
+    ("greenyellow", [0xad, 0xff, 0x2f]),
+    ("grey", [0x80, 0x80, 0x80]),
+    ("honeydew", [0xf0, 0xff, 0xf0]),
+    ("hotpink", [0xff, 0x69, 0xb4]),
+    ("indianred", [0xcd, 0x5c, 0x5c]),
+    ("indigo", [0x4b, 0x00, 0x82]),
+    ("ivory", [0xff, 0xff, 0xf0]),
+    ("khaki", [0xf0, 0xe6, 0x8c]),
+    ("lavender", [0xe6, 0xe6, 0xfa]),
+    ("lavenderblush", [0xff, 0xf0, 0xf5]),
+    ("lawngreen", [0x7c, 0xfc, 0x00]),
+    ("lemonchiffon", [0xff, 0xfa, 0xcd]),
+    ("lightblue", [0xad, 0xd8, 0xe6]),
+    ("lightcoral", [0xf0, 0x80, 0x80]),
+    ("lightcyan", [0xe0, 0xff, 0xff]),
+    ("lightgoldenrodyellow", [0xfa, 0xfa, 0xd2]),
+    ("lightgray", [0xd3, 0xd3, 0xd3]),
+    ("lightgreen", [0x90, 0xee, 0x90]),
+    ("lightgrey", [0xd3, 0xd3, 0xd3]),
+    ("lightpink", [0xff, 0xb6, 0xc1]),
+    ("lightsalmon", [0xff, 0xa0, 0x7a]),
+    ("lightseagreen", [0x20, 0xb2, 0xaa]),
+    ("lightskyblue", [0x87, 0xce, 0xfa]),
+    ("lightslategray", [0x77, 0x88, 0x99]),
+    ("lightslategrey", [0x77, 0x88, 0x99]),
+    ("lightsteelblue", [0xb0, 0xc4, 0xde]),
+    ("lightyellow", [0xff, 0xff, 0xe0]),
+    ("lime", [0x00, 0xff, 0x00]),
+    ("limegreen", [0x32, 0xcd, 0x32]),
+    ("linen", [0xfa, 0xf0, 0xe6]),
+    ("magenta", [0xff, 0x00, 0xff]),
+    ("maroon", [0x80, 0x00, 0x00]),
+    ("mediumaquamarine", [0x66, 0xcd, 0xaa]),
+    ("mediumblue", [0x00, 0x00, 0xcd]),
+    ("mediumorchid", [0xba, 0x55, 0xd3]),
+    ("mediumpurple", [0x93, 0x70, 0xdb]),
+    ("mediumseagreen", [0x3c, 0xb3, 0x71]),
+    ("mediumslateblue", [0x7b, 0x68, 0xee]),
+    ("mediumspringgreen", [0x00, 0xfa, 0x9a]),
+    ("mediumturquoise", [0x48, 0xd1, 0xcc]),
+    ("mediumvioletred", [0xc7, 0x15, 0x85]),
+    ("midnightblue", [0x19, 0x19, 0x70]),
+    ("mintcream", [0xf5, 0xff, 0xfa]),
+    ("mistyrose", [0xff, 0xe4, 0xe1]),
+    ("moccasin", [0xff, 0xe4, 0xb5]),
+    ("navajowhite", [0xff, 0xde, 0xad]),
+    ("navy", [0x00, 0x00, 0x80]),
+    ("oldlace", [0xfd, 0xf5, 0xe6]),
+    ("olive", [0x80, 0x80, 0x00]),
+    ("olivedrab", [0x6b, 0x8e, 0x23]),
+    ("orange", [0xff, 0xa5, 0x00]),
+    ("orangered", [0xff, 0x45, 0x00]),
+    ("orchid", [0xda, 0x70, 0xd6]),
+    ("palegoldenrod", [0xee, 0xe8, 0xaa]),
+    ("palegreen", [0x98, 0xfb, 0x98]),
+    ("paleturquoise", [0xaf, 0xee, 0xee]),
+    ("palevioletred", [0xdb, 0x70, 0x93]),
+    ("papayawhip", [0xff, 0xef, 0xd5]),
+    ("peachpuff", [0xff, 0xda, 0xb9]),
+    ("peru", [0xcd, 0x85, 0x3f]),
+    ("pink", [0xff, 0xc0, 0xcb]),
+    ("plum", [0xdd, 0xa0, 0xdd]),
+    ("powderblue", [0xb0, 0xe0, 0xe6]),
+    ("purple", [0x80, 0x00, 0x80]),
+    ("rebeccapurple", [0x66, 0x33, 0x99]),
+    ("red", [0xff, 0x00, 0x00]),
+    ("rosybrown", [0xbc, 0x8f, 0x8f]),
+    ("royalblue", [0x41, 0x69, 0xe1]),
+    ("saddlebrown", [0x8b, 0x45, 0x13]),
+    ("salmon", [0xfa, 0x80, 0x72]),
+    ("sandybrown", [0xf4, 0xa4, 0x60]),
+    ("seagreen", [0x2e, 0x8b, 0x57]),
+    ("seashell", [0xff, 0xf5, 0xee]),
+    ("sienna", [0xa0, 0x52, 0x2d]),
+    ("silver", [0xc0, 0xc0, 0xc0]),
+    ("skyblue", [0x87, 0xce, 0xeb]),
+    ("slateblue", [0x6a, 0x5a, 0xcd]),
+    ("slategray", [0x70, 0x80, 0x90]),
+    ("slategrey", [0x70, 0x80, 0x90]),
+    ("snow", [0xff, 0xfa, 0xfa]),
+    ("springgreen", [0x00, 0xff, 0x7f]),
+    ("steelblue", [0x46, 0x82, 0xb4]),
+    ("tan", [0xd2, 0xb4, 0x8c]),
+    ("teal", [0x00, 0x80, 0x80]),
+    ("thistle", [0xd8, 0xbf, 0xd8]),
+    ("tomato", [0xff, 0x63, 0x47]),
+    ("turquoise", [0x40, 0xe0, 0xd0]),
+    ("violet", [0xee, 0x82, 0xee]),
+    ("wheat", [0xf5, 0xde, 0xb3]),
+    ("white", [0xff, 0xff, 0xff]),
+    ("whitesmoke", [0xf5, 0xf5, 0xf5]),
+    ("yellow", [0xff, 0xff, 0x00]),
+    ("yellowgreen", [0x9a, 0xcd, 0x32]),
+];
+
+#[test]
+fn parsing() {
+    assert!(NAMED_COLORS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    assert_eq!(NAMED_COLORS.len(), 148);
+
+    let cornflower = Color::rgb8(0x64, 0x95, 0xed);
+    for source in [
+        "#6495ed",
+        "#6495EDff",
+        "cornflowerblue",
+        "rgb(100, 149, 237)",
+        "rgba(100 149 237 / 100%)",
+    ] {
+        assert_eq!(source.parse::<Color>(), Ok(cornflower), "{source}");
+    }
+    assert_eq!(
+        "#0f08".parse::<Color>().unwrap().to_rgba8(),
+        [0, 255, 0, 136]
+    );
+    assert_eq!(
+        "hsla(120deg 100% 25% / 0.5)"
+            .parse::<Color>()
+            .unwrap()
+            .to_rgba8(),
+        [0, 128, 0, 128]
+    );
+    assert_eq!("transparent".parse(), Ok(Color::TRANSPARENT));
+    assert_eq!("#12345".parse::<Color>(), Err(ParseColorError::InvalidHex));
+    assert_eq!(
+        "notacolor".parse::<Color>(),
+        Err(ParseColorError::UnknownColor)
+    );
+    assert_eq!(
+        "rgb(1, 2)".parse::<Color>(),
+        Err(ParseColorError::InvalidSyntax)
+    );
+    assert_eq!(cornflower.to_string(), "#6495ed");
+    assert_eq!(cornflower.with_alpha(0.).to_string(), "#6495ed00");
+}
+
+#[test]
+fn conversions() {
+    for color in [
+        Color::rgb8(255, 0, 0),
+        Color::rgb8(12, 200, 99),
+        Color::rgba8(1, 2, 3, 4),
+        Color::WHITE,
+        Color::BLACK,
+    ] {
+        assert_eq!(color.to_linear().to_srgb().to_rgba8(), color.to_rgba8());
+        assert_eq!(color.to_oklch().to_srgb().to_rgba8(), color.to_rgba8());
+    }
+    let white = Color::WHITE.to_oklch();
+    assert!((white.lightness - 1.).abs() < 0.001);
+    assert!(white.chroma < 0.001);
+}
+
+#[test]
+fn compositing_and_interpolation() {
+    let half_red = Color::rgb(1., 0., 0.).with_alpha(0.5);
+    assert_eq!(
+        half_red.over(Color::rgb(0., 0., 1.)).to_rgba8(),
+        [128, 0, 128, 255]
+    );
+    assert_eq!(
+        half_red.over(Color::TRANSPARENT).to_rgba8(),
+        half_red.to_rgba8()
+    );
+
+    let black = Color::BLACK;
+    let white = Color::WHITE;
+    assert_eq!(
+        black.interpolate(white, 0.5, ColorSpace::Srgb).to_rgba8(),
+        [128, 128, 128, 255]
+    );
+    assert_eq!(
+        black
+            .interpolate(white, 0.5, ColorSpace::LinearRgb)
+            .to_rgba8(),
+        [188, 188, 188, 255]
+    );
+    for space in [ColorSpace::Srgb, ColorSpace::LinearRgb, ColorSpace::Oklch] {
+        assert_eq!(
+            black.interpolate(white, 0., space).to_rgba8(),
+            black.to_rgba8()
+        );
+        assert_eq!(
+            black.interpolate(white, 1., space).to_rgba8(),
+            white.to_rgba8()
+        );
+    }
+    // Interpolating from a transparent color only changes the opacity.
+    assert_eq!(
+        Color::TRANSPARENT
+            .interpolate(half_red, 0.5, ColorSpace::Srgb)
+            .to_rgba8(),
+        [255, 0, 0, 64]
+    );
+}
+
+#[test]
+fn components() {
+    let parent = crate::Style::new()
+        .with(TextColor(Color::WHITE))
+        .with(BackgroundColor(Color::BLACK));
+    let child = crate::Style::new().inherited_from(&parent);
+    assert_eq!(child.get::<TextColor>(), Some(&TextColor(Color::WHITE)));
+    assert_eq!(child.get::<BackgroundColor>(), None);
+}
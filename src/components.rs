@@ -2,7 +2,7 @@ use std::any::Any;
 use std::fmt::Debug;
 use std::panic::{RefUnwindSafe, UnwindSafe};
 
-use crate::{Identifier, Name};
+use crate::{ComponentId, Identifier, InvalidValue, Name, ResolutionContext};
 
 /// A style component. Implementors can be stored within
 /// [`Style`](crate::Style).
@@ -19,6 +19,25 @@ use crate::{Identifier, Name};
 /// - `authority`: An identifier. By default, this is [`Identifier::private()`].
 /// - `merge`: An expression to evaluate when merging. `self` and `other` are
 ///   defined. By default, components do not merge.
+/// - `resolve_units`: An expression to evaluate when resolving relative units.
+///   `self` and `context` are defined. By default, nothing is resolved.
+/// - `initial`: An expression producing the component's initial value. By
+///   default, components have no initial value.
+/// - `validate`: An expression evaluating to `Result<(), InvalidValue>` that
+///   checks whether `self` is valid. By default, all values are valid.
+/// - `normalize`: An expression to evaluate when normalizing `self`. By
+///   default, nothing is normalized.
+/// - `resolve_against_parent`: An expression to evaluate when resolving a
+///   value relative to the parent's value. `self` and `parent` are defined. By
+///   default, nothing is resolved.
+/// - `equivalent`: A boolean expression comparing `self` and `other`. By
+///   default, values are never considered equivalent.
+/// - `partial_eq`: A flag that implements `equivalent` using the type's
+///   [`PartialEq`] implementation. Cannot be combined with `equivalent`.
+///
+/// Fields may also be annotated with `#[style(range = expr)]`. The generated
+/// validation ensures each annotated field is contained within the range before
+/// evaluating the `validate` expression.
 pub trait StyleComponent: Any + RefUnwindSafe + UnwindSafe + Send + Sync + Debug + 'static {
     /// The unique name of this style component.
     ///
@@ -40,11 +59,29 @@ pub trait StyleComponent: Any + RefUnwindSafe + UnwindSafe + Send + Sync + Debug
 
     /// Returns the authority of this component. By default, this returns
     /// [`Identifier::private()`].
+    ///
+    /// The `stylecs` authority is reserved for components provided by this
+    /// crate.
     #[must_use]
     fn authority() -> Identifier {
         Identifier::private()
     }
 
+    /// Returns the [`ComponentId`] for this type, used to efficiently look up
+    /// this component in a [`Style`](crate::Style).
+    ///
+    /// The provided implementation returns [`ComponentId::of::<Self>()`],
+    /// which caches the id for each type in a thread-local map.
+    /// Implementations can cache the result in a static to avoid this lookup.
+    /// The derive macro does this for types without generic parameters.
+    #[must_use]
+    fn component_id() -> ComponentId
+    where
+        Self: Sized,
+    {
+        ComponentId::of::<Self>()
+    }
+
     /// Returns whether the component should be inherited. Affects the behavior
     /// of [`Style::inherited_from`](crate::Style::inherited_from)
     ///
@@ -54,10 +91,76 @@ pub trait StyleComponent: Any + RefUnwindSafe + UnwindSafe + Send + Sync + Debug
         false
     }
 
+    /// Returns the value this component has when no value has been specified.
+    /// Used by [`InitialValues::register`](crate::InitialValues::register).
+    ///
+    /// This provided implementation returns `None`.
+    #[must_use]
+    fn initial_value() -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
     /// Merges `self` with `other`, if it makes sense to do so for this type.
     /// The default implementation does nothing, preserving the `self` value.
     #[allow(unused_variables)]
     fn merge(&mut self, other: &Self) {}
+
+    /// Converts any relative [`Dimension`](crate::Dimension)s contained in
+    /// `self` into absolute dimensions using `context`. Affects the behavior
+    /// of [`Style::resolve_units`](crate::Style::resolve_units).
+    ///
+    /// The default implementation does nothing.
+    #[allow(unused_variables)]
+    fn resolve_units(&mut self, context: &ResolutionContext) {}
+
+    /// Resolves `self` relative to `parent`, the value of this component in
+    /// the parent style. Affects the behavior of
+    /// [`Style::inherited_from`](crate::Style::inherited_from), which invokes
+    /// this function when both the child and parent styles contain this
+    /// component, regardless of whether the component is
+    /// [inherited](Self::inherited).
+    ///
+    /// This allows values such as a font size scale or an opacity multiplier
+    /// to be converted into an absolute value. The default implementation
+    /// does nothing.
+    #[allow(unused_variables)]
+    fn resolve_against_parent(&mut self, parent: &Self) {}
+
+    /// Adjusts `self` into a canonical, valid form, such as by clamping values
+    /// into their allowed range. Invoked by
+    /// [`Style::push_checked`](crate::Style::push_checked) before the value is
+    /// validated.
+    ///
+    /// The default implementation does nothing.
+    fn normalize(&mut self) {}
+
+    /// Checks whether `self` is a valid value for this component.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidValue`] describing why `self` is invalid. The
+    /// default implementation accepts all values.
+    fn validate(&self) -> Result<(), InvalidValue> {
+        Ok(())
+    }
+
+    /// Returns true if `self` and `other` represent the same value. Used to
+    /// detect conflicts when applying a
+    /// [`StylePatch`](crate::StylePatch).
+    ///
+    /// The default implementation returns false, treating every value as
+    /// changed: merges and replacements always advance
+    /// [generations](crate::Style::generation) and notify observers, and
+    /// patches expecting a value of this component always conflict. Types
+    /// implementing [`PartialEq`] should override this to compare using `==`.
+    #[must_use]
+    #[allow(unused_variables)]
+    fn equivalent(&self, other: &Self) -> bool {
+        false
+    }
 }
 
 /// A style component that can be powered by data contained in the structure.
@@ -76,6 +179,12 @@ pub trait DynamicComponent:
     /// unique [authority](Name::authority) based on the crate it comes from.
     fn name(&self) -> Name;
 
+    /// Returns the [`ComponentId`] for this component's [`name`](Self::name).
+    #[must_use]
+    fn component_id(&self) -> ComponentId {
+        ComponentId::for_name(&self.name())
+    }
+
     /// Returns whether the component should be inherited. Affects the behavior
     /// of [`Style::inherited_from`](crate::Style::inherited_from)
     #[must_use]
@@ -88,6 +197,45 @@ pub trait DynamicComponent:
     /// self.
     #[allow(unused_variables)]
     fn merge(&mut self, other: &Self) {}
+
+    /// Converts any relative [`Dimension`](crate::Dimension)s contained in
+    /// `self` into absolute dimensions using `context`.
+    ///
+    /// The default implementation does nothing.
+    #[allow(unused_variables)]
+    fn resolve_units(&mut self, context: &ResolutionContext) {}
+
+    /// Resolves `self` relative to `parent`, the value of this component in
+    /// the parent style.
+    ///
+    /// The default implementation does nothing.
+    #[allow(unused_variables)]
+    fn resolve_against_parent(&mut self, parent: &Self) {}
+
+    /// Adjusts `self` into a canonical, valid form.
+    ///
+    /// The default implementation does nothing.
+    fn normalize(&mut self) {}
+
+    /// Checks whether `self` is a valid value for this component.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidValue`] describing why `self` is invalid. The
+    /// default implementation accepts all values.
+    fn validate(&self) -> Result<(), InvalidValue> {
+        Ok(())
+    }
+
+    /// Returns true if `self` and `other` represent the same value.
+    ///
+    /// The default implementation returns false, treating every value as
+    /// changed. See [`StyleComponent::equivalent`].
+    #[must_use]
+    #[allow(unused_variables)]
+    fn equivalent(&self, other: &Self) -> bool {
+        false
+    }
 }
 
 impl<T> DynamicComponent for T
@@ -98,6 +246,10 @@ where
         T::name()
     }
 
+    fn component_id(&self) -> ComponentId {
+        T::component_id()
+    }
+
     fn inherited(&self) -> bool {
         T::inherited()
     }
@@ -105,4 +257,24 @@ where
     fn merge(&mut self, other: &Self) {
         <T as StyleComponent>::merge(self, other);
     }
+
+    fn resolve_units(&mut self, context: &ResolutionContext) {
+        <T as StyleComponent>::resolve_units(self, context);
+    }
+
+    fn resolve_against_parent(&mut self, parent: &Self) {
+        <T as StyleComponent>::resolve_against_parent(self, parent);
+    }
+
+    fn normalize(&mut self) {
+        <T as StyleComponent>::normalize(self);
+    }
+
+    fn validate(&self) -> Result<(), InvalidValue> {
+        <T as StyleComponent>::validate(self)
+    }
+
+    fn equivalent(&self, other: &Self) -> bool {
+        <T as StyleComponent>::equivalent(self, other)
+    }
 }
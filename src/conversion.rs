@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use kempt::Map;
+
+use crate::any::AnyComponent;
+use crate::{ComponentId, DynamicValue, InvalidValue, Name, ScriptValue, StyleComponent};
+
+/// A registry of conversions between [`DynamicValue`]s and typed
+/// [`StyleComponent`]s, keyed by the component's [`Name`].
+///
+/// Styles loaded from scripts or files contain [`DynamicValue`]s, which can be
+/// converted into their typed counterparts using
+/// [`Style::upgrade`](crate::Style::upgrade). Typed components can be
+/// converted back for export using
+/// [`Style::downgrade`](crate::Style::downgrade).
+#[derive(Default, Clone)]
+pub struct ConversionRegistry {
+    conversions: Map<ComponentId, Conversion>,
+}
+
+type Upgrade = dyn Fn(&ScriptValue) -> Result<AnyComponent, InvalidValue> + Send + Sync;
+type Downgrade = dyn Fn(&AnyComponent) -> Option<ScriptValue> + Send + Sync;
+
+#[derive(Clone)]
+struct Conversion {
+    upgrade: Arc<Upgrade>,
+    downgrade: Arc<Downgrade>,
+}
+
+impl std::fmt::Debug for ConversionRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.conversions.iter().map(|field| field.key().name()))
+            .finish()
+    }
+}
+
+impl ConversionRegistry {
+    /// Returns an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers conversions between [`DynamicValue`]s named
+    /// [`T::name()`](StyleComponent::name) and `T`, replacing any previously
+    /// registered conversions for `T`.
+    ///
+    /// `upgrade` converts a dynamic value into `T`, and `downgrade` converts
+    /// `T` into a dynamic value.
+    pub fn register<T: StyleComponent + Clone>(
+        &mut self,
+        upgrade: impl Fn(&ScriptValue) -> Result<T, InvalidValue> + Send + Sync + 'static,
+        downgrade: impl Fn(&T) -> ScriptValue + Send + Sync + 'static,
+    ) {
+        self.conversions.insert(
+            T::component_id(),
+            Conversion {
+                upgrade: Arc::new(move |value| upgrade(value).map(AnyComponent::new)),
+                downgrade: Arc::new(move |component| component.get::<T>().map(&downgrade)),
+            },
+        );
+    }
+
+    /// Registers conversions between [`DynamicValue`]s and `T` and returns
+    /// self.
+    #[must_use]
+    pub fn with<T: StyleComponent + Clone>(
+        mut self,
+        upgrade: impl Fn(&ScriptValue) -> Result<T, InvalidValue> + Send + Sync + 'static,
+        downgrade: impl Fn(&T) -> ScriptValue + Send + Sync + 'static,
+    ) -> Self {
+        self.register(upgrade, downgrade);
+        self
+    }
+
+    /// Returns true if a conversion is registered for the component named
+    /// `name`.
+    #[must_use]
+    pub fn contains(&self, name: &Name) -> bool {
+        ComponentId::find(name).map_or(false, |id| self.conversions.contains(&id))
+    }
+
+    /// Converts `value` into its typed component. Returns `None` if no
+    /// conversion is registered for the value's name, or the result of the
+    /// registered conversion.
+    #[must_use]
+    pub fn upgrade(&self, value: &DynamicValue) -> Option<Result<AnyComponent, InvalidValue>> {
+        let conversion = self.conversions.get(&ComponentId::find(&value.name)?)?;
+        Some((conversion.upgrade)(&value.value))
+    }
+
+    /// Converts `component` into a [`DynamicValue`]. Returns `None` if no
+    /// conversion is registered for the component.
+    #[must_use]
+    pub fn downgrade(&self, component: &AnyComponent) -> Option<DynamicValue> {
+        let conversion = self.conversions.get(&component.component_id())?;
+        let value = (conversion.downgrade)(component)?;
+        Some(DynamicValue::new(component.name(), value).with_inherited(component.inherited()))
+    }
+
+    /// Returns the number of registered conversions.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.conversions.len()
+    }
+
+    /// Returns true if no conversions are registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.conversions.is_empty()
+    }
+}
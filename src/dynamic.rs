@@ -0,0 +1,172 @@
+use std::collections::BTreeMap;
+
+use crate::{DynamicComponent, Name};
+
+/// A [`DynamicComponent`] whose name and value are determined at runtime.
+///
+/// This type allows integrations such as scripting languages to store
+/// arbitrary properties in a [`Style`](crate::Style) without defining their
+/// own component types. Because its name is only known at runtime, a
+/// `DynamicValue` is retrieved using
+/// [`Style::get_by_name`](crate::Style::get_by_name).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynamicValue {
+    /// The name of this component.
+    pub name: Name,
+    /// The value of this component.
+    pub value: ScriptValue,
+    /// Whether this component is inherited. `false` by default.
+    pub inherited: bool,
+    /// How this component is merged with another value of the same name.
+    pub merge: MergeStrategy,
+}
+
+/// A value stored in a [`DynamicValue`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum ScriptValue {
+    /// No value.
+    #[default]
+    Null,
+    /// A boolean.
+    Bool(bool),
+    /// A signed integer.
+    Int(i64),
+    /// A floating point number.
+    Float(f64),
+    /// A string.
+    String(String),
+    /// A list of values.
+    List(Vec<ScriptValue>),
+    /// A map of string keys to values.
+    Map(BTreeMap<String, ScriptValue>),
+}
+
+/// Controls how a [`DynamicValue`] is merged with another value of the same
+/// name.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum MergeStrategy {
+    /// The existing value is kept.
+    #[default]
+    Replace,
+    /// When both values are maps, entries missing from the existing map are
+    /// copied from the other map. Entries present in both maps are merged
+    /// recursively when both are maps.
+    DeepMerge,
+    /// When both values are lists, the other list's values are appended to
+    /// the existing list.
+    Concatenate,
+}
+
+impl DynamicValue {
+    /// Returns a non-inherited component named `name` containing `value`,
+    /// using [`MergeStrategy::Replace`].
+    pub fn new(name: Name, value: impl Into<ScriptValue>) -> Self {
+        Self {
+            name,
+            value: value.into(),
+            inherited: false,
+            merge: MergeStrategy::Replace,
+        }
+    }
+
+    /// Sets whether this component is inherited and returns self.
+    #[must_use]
+    pub fn with_inherited(mut self, inherited: bool) -> Self {
+        self.inherited = inherited;
+        self
+    }
+
+    /// Sets how this component is merged and returns self.
+    #[must_use]
+    pub fn with_merge(mut self, merge: MergeStrategy) -> Self {
+        self.merge = merge;
+        self
+    }
+}
+
+impl DynamicComponent for DynamicValue {
+    fn name(&self) -> Name {
+        self.name.clone()
+    }
+
+    fn inherited(&self) -> bool {
+        self.inherited
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.value.merge(&other.value, self.merge);
+    }
+
+    fn equivalent(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+impl ScriptValue {
+    /// Returns true if this value is [`ScriptValue::Null`].
+    #[must_use]
+    pub const fn is_null(&self) -> bool {
+        matches!(self, ScriptValue::Null)
+    }
+
+    /// Merges `other` into `self` using `strategy`.
+    pub fn merge(&mut self, other: &Self, strategy: MergeStrategy) {
+        match (strategy, self, other) {
+            (MergeStrategy::DeepMerge, ScriptValue::Map(mine), ScriptValue::Map(other)) => {
+                for (key, value) in other {
+                    if let Some(existing) = mine.get_mut(key) {
+                        existing.merge(value, strategy);
+                    } else {
+                        mine.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+            (MergeStrategy::Concatenate, ScriptValue::List(mine), ScriptValue::List(other)) => {
+                mine.extend(other.iter().cloned());
+            }
+            _ => {}
+        }
+    }
+}
+
+impl From<bool> for ScriptValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i64> for ScriptValue {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<f64> for ScriptValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<String> for ScriptValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for ScriptValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<Vec<ScriptValue>> for ScriptValue {
+    fn from(value: Vec<ScriptValue>) -> Self {
+        Self::List(value)
+    }
+}
+
+impl From<BTreeMap<String, ScriptValue>> for ScriptValue {
+    fn from(value: BTreeMap<String, ScriptValue>) -> Self {
+        Self::Map(value)
+    }
+}
@@ -0,0 +1,244 @@
+use crate::any::AnyComponent;
+use crate::components::DynamicComponent;
+use crate::style::{equivalent, Value};
+use crate::{ComponentId, Name, Style, StyleComponent};
+
+/// A [`Style`] that records each edit so that it can be undone and redone.
+///
+/// Each edit stores only the values of the components it changed. Edits made
+/// within [`StyleHistory::transaction`] are undone and redone as a single
+/// step.
+#[derive(Debug, Default)]
+pub struct StyleHistory {
+    style: Style,
+    undo: Vec<Transaction>,
+    redo: Vec<Transaction>,
+    open: Option<Transaction>,
+    last_checkpoint: u64,
+}
+
+#[derive(Default)]
+struct Transaction {
+    checkpoint: Checkpoint,
+    edits: Vec<Edit>,
+}
+
+struct Edit {
+    id: ComponentId,
+    before: Option<Value>,
+    after: Option<Value>,
+}
+
+impl std::fmt::Debug for Transaction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Transaction")
+            .field("checkpoint", &self.checkpoint)
+            .field("edits", &self.edits.len())
+            .finish()
+    }
+}
+
+/// Records the open transaction of a [`StyleHistory`] when dropped, even if
+/// the transaction's closure panicked.
+struct OpenTransaction<'a>(&'a mut StyleHistory);
+
+impl Drop for OpenTransaction<'_> {
+    fn drop(&mut self) {
+        if let Some(transaction) = self.0.open.take() {
+            self.0.record(transaction);
+        }
+    }
+}
+
+/// A point in a [`StyleHistory`] that can be returned to using
+/// [`StyleHistory::revert_to`].
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Checkpoint(u64);
+
+impl StyleHistory {
+    /// Returns a new history whose initial state is `style`.
+    #[must_use]
+    pub fn new(style: Style) -> Self {
+        Self {
+            style,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the current style.
+    #[must_use]
+    pub const fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Returns the current style, discarding the history.
+    #[must_use]
+    pub fn into_style(self) -> Style {
+        self.style
+    }
+
+    /// Adds a component to the style. Any existing values of the same type
+    /// will be replaced.
+    pub fn push<T: DynamicComponent + Clone>(&mut self, component: T) {
+        let id = component.component_id();
+        self.edit([id], |style| style.push(component));
+    }
+
+    /// Removes and returns the style component of type `T`, if present.
+    pub fn remove<T: StyleComponent>(&mut self) -> Option<T> {
+        self.style.get::<T>()?;
+        self.remove_by_id(T::component_id())
+            .and_then(|component| component.into_inner().ok())
+    }
+
+    /// Removes and returns the style component with the given name, if
+    /// present. A [`Keyword`](crate::Keyword) stored for this name is also
+    /// removed.
+    pub fn remove_by_name(&mut self, name: &Name) -> Option<AnyComponent> {
+        self.remove_by_id(ComponentId::find(name)?)
+    }
+
+    fn remove_by_id(&mut self, id: ComponentId) -> Option<AnyComponent> {
+        self.style.value(id)?;
+        self.edit([id], |style| style.remove_by_id(id))
+    }
+
+    /// Merges `other` into the style using the same rules as
+    /// [`Style::merged_with`].
+    pub fn merge(&mut self, other: &Style) {
+        self.edit(other.ids().collect::<Vec<_>>(), |style| {
+            *style = std::mem::take(style).merged_with(other);
+        });
+    }
+
+    /// Invokes `edits` and records every edit it makes as a single step that
+    /// is undone and redone together.
+    ///
+    /// Nested transactions are combined into the outermost transaction. If
+    /// `edits` panics, the edits made before the panic are still recorded as
+    /// a single step.
+    pub fn transaction<R>(&mut self, edits: impl FnOnce(&mut Self) -> R) -> R {
+        if self.open.is_some() {
+            return edits(self);
+        }
+        self.open = Some(Transaction::default());
+        let guard = OpenTransaction(self);
+        edits(guard.0)
+    }
+
+    fn edit<R>(
+        &mut self,
+        ids: impl IntoIterator<Item = ComponentId>,
+        edit: impl FnOnce(&mut Style) -> R,
+    ) -> R {
+        let ids = ids.into_iter().collect::<Vec<_>>();
+        let before = ids
+            .iter()
+            .map(|id| self.style.value(*id).cloned())
+            .collect::<Vec<_>>();
+        let result = edit(&mut self.style);
+        let edits = ids
+            .into_iter()
+            .zip(before)
+            .filter(|(id, before)| !equivalent(before.as_ref(), self.style.value(*id)))
+            .map(|(id, before)| Edit {
+                id,
+                before,
+                after: self.style.value(id).cloned(),
+            });
+        if let Some(open) = &mut self.open {
+            open.edits.extend(edits);
+        } else {
+            let transaction = Transaction {
+                checkpoint: Checkpoint::default(),
+                edits: edits.collect(),
+            };
+            self.record(transaction);
+        }
+        result
+    }
+
+    fn record(&mut self, mut transaction: Transaction) {
+        if transaction.edits.is_empty() {
+            return;
+        }
+        self.last_checkpoint += 1;
+        transaction.checkpoint = Checkpoint(self.last_checkpoint);
+        self.undo.push(transaction);
+        self.redo.clear();
+    }
+
+    /// Reverts the most recent step. Returns false if there is nothing to
+    /// undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(transaction) = self.undo.pop() else {
+            return false;
+        };
+        for edit in transaction.edits.iter().rev() {
+            self.style.restore_value(edit.id, edit.before.clone());
+        }
+        self.redo.push(transaction);
+        true
+    }
+
+    /// Reapplies the most recently undone step. Returns false if there is
+    /// nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(transaction) = self.redo.pop() else {
+            return false;
+        };
+        for edit in &transaction.edits {
+            self.style.restore_value(edit.id, edit.after.clone());
+        }
+        self.undo.push(transaction);
+        true
+    }
+
+    /// Returns true if there is a step that can be undone.
+    #[must_use]
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Returns true if there is a step that can be redone.
+    #[must_use]
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Returns a checkpoint representing the current state of the style.
+    #[must_use]
+    pub fn checkpoint(&self) -> Checkpoint {
+        self.undo
+            .last()
+            .map_or(Checkpoint::default(), |transaction| transaction.checkpoint)
+    }
+
+    /// Undoes or redoes steps until the style is in the state it was in when
+    /// `checkpoint` was created. Returns false if the checkpoint is no longer
+    /// reachable because the steps after it were replaced by new edits.
+    pub fn revert_to(&mut self, checkpoint: Checkpoint) -> bool {
+        if checkpoint == Checkpoint::default()
+            || self
+                .undo
+                .iter()
+                .any(|transaction| transaction.checkpoint == checkpoint)
+        {
+            while self.checkpoint() != checkpoint {
+                self.undo();
+            }
+            true
+        } else if self
+            .redo
+            .iter()
+            .any(|transaction| transaction.checkpoint == checkpoint)
+        {
+            while self.checkpoint() != checkpoint {
+                self.redo();
+            }
+            true
+        } else {
+            false
+        }
+    }
+}
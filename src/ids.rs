@@ -0,0 +1,329 @@
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
+use std::sync::{OnceLock, PoisonError, RwLock};
+
+use crate::{Identifier, Name, StyleComponent};
+
+/// A dense, process-wide identifier for a component [`Name`].
+///
+/// Ids are assigned sequentially the first time a name is used, allowing
+/// [`Style`](crate::Style) to look up components without comparing names. Ids
+/// are only stable for the lifetime of the process and should not be
+/// persisted.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ComponentId(u32);
+
+#[derive(Default)]
+struct Registry {
+    by_name: HashMap<Name, ComponentId>,
+    by_type: HashMap<TypeId, ComponentId>,
+    by_authority: HashMap<Identifier, ComponentSet>,
+    names: Vec<Name>,
+}
+
+impl Registry {
+    fn register(&mut self, name: &Name) -> ComponentId {
+        if let Some(id) = self.by_name.get(name) {
+            return *id;
+        }
+        let id = ComponentId(u32::try_from(self.names.len()).expect("too many components"));
+        self.names.push(name.clone());
+        self.by_name.insert(name.clone(), id);
+        self.by_authority
+            .entry(name.authority.clone())
+            .or_default()
+            .insert(id);
+        id
+    }
+}
+
+thread_local! {
+    static TYPE_IDS: RefCell<HashMap<TypeId, ComponentId>> = RefCell::default();
+}
+
+fn registry() -> &'static RwLock<Registry> {
+    static REGISTRY: OnceLock<RwLock<Registry>> = OnceLock::new();
+    REGISTRY.get_or_init(RwLock::default)
+}
+
+impl ComponentId {
+    /// Returns the id of the component type `T`, assigning one if this is the
+    /// first time `T` or its [`Name`] has been used.
+    ///
+    /// If `T`'s name is an [alias](Name::register_alias), the id of its
+    /// canonical name is returned.
+    ///
+    /// This does not invoke [`StyleComponent::component_id`], allowing
+    /// implementations of that function to cache the result of this function.
+    /// Ids are also cached per thread, so only the first lookup of `T` on each
+    /// thread accesses the global registry.
+    #[must_use]
+    pub fn of<T: StyleComponent>() -> Self {
+        let type_id = TypeId::of::<T>();
+        if let Some(id) = TYPE_IDS
+            .try_with(|ids| ids.borrow().get(&type_id).copied())
+            .ok()
+            .flatten()
+        {
+            return id;
+        }
+
+        let registered = registry()
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .by_type
+            .get(&type_id)
+            .copied();
+        let id = registered.unwrap_or_else(|| {
+            let name = T::name().into_canonical();
+            let mut registry = registry().write().unwrap_or_else(PoisonError::into_inner);
+            let id = registry.register(&name);
+            registry.by_type.insert(type_id, id);
+            id
+        });
+        // The cache is unavailable while the thread is being destroyed.
+        let _ = TYPE_IDS.try_with(|ids| ids.borrow_mut().insert(type_id, id));
+        id
+    }
+
+    /// Returns the id of `name`, assigning one if this is the first time
+    /// `name` has been used.
+    ///
+    /// If `name` is an [alias](Name::register_alias), the id of its
+    /// canonical name is returned.
+    #[must_use]
+    pub fn for_name(name: &Name) -> Self {
+        let name = name.canonical();
+        if let Some(id) = Self::find_canonical(&name) {
+            id
+        } else {
+            registry()
+                .write()
+                .unwrap_or_else(PoisonError::into_inner)
+                .register(&name)
+        }
+    }
+
+    /// Returns the id of `name`, if one has been assigned.
+    ///
+    /// If `name` is an [alias](Name::register_alias), the id of its
+    /// canonical name is returned. A name without an id cannot be contained
+    /// in any [`Style`](crate::Style).
+    #[must_use]
+    pub fn find(name: &Name) -> Option<Self> {
+        Self::find_canonical(&name.canonical())
+    }
+
+    fn find_canonical(name: &Name) -> Option<Self> {
+        registry()
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .by_name
+            .get(name)
+            .copied()
+    }
+
+    /// Returns the name this id was assigned to.
+    #[must_use]
+    pub fn name(self) -> Name {
+        registry()
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .names[self.index()]
+        .clone()
+    }
+
+    /// Returns the index of this id. Ids are assigned sequentially starting at
+    /// 0, making this value suitable for indexing into dense collections.
+    #[must_use]
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl Debug for ComponentId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ComponentId")
+            .field(&self.0)
+            .field(&format_args!("{}", self.name()))
+            .finish()
+    }
+}
+
+impl Display for ComponentId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.name(), f)
+    }
+}
+
+/// A compact set of [`ComponentId`]s.
+///
+/// This type is a bitset indexed by [`ComponentId::index`], making
+/// intersection and subset checks proportional to the number of registered
+/// components rather than the number of components in the set.
+#[derive(Default, Clone, Eq, PartialEq, Hash)]
+pub struct ComponentSet {
+    /// The bits of this set. The last word is never 0, ensuring equal sets
+    /// have equal representations.
+    words: Vec<u64>,
+}
+
+impl ComponentSet {
+    /// Returns an empty set.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { words: Vec::new() }
+    }
+
+    /// Adds the component `T` to this set and returns self.
+    #[must_use]
+    pub fn with<T: StyleComponent>(mut self) -> Self {
+        self.insert(T::component_id());
+        self
+    }
+
+    /// Adds `id` to this set. Returns true if `id` was not already present.
+    pub fn insert(&mut self, id: ComponentId) -> bool {
+        let (word, bit) = Self::position(id);
+        if self.words.len() <= word {
+            self.words.resize(word + 1, 0);
+        }
+        let inserted = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        inserted
+    }
+
+    /// Removes `id` from this set. Returns true if `id` was present.
+    pub fn remove(&mut self, id: ComponentId) -> bool {
+        let (word, bit) = Self::position(id);
+        let Some(bits) = self.words.get_mut(word) else {
+            return false;
+        };
+        let removed = *bits & bit != 0;
+        *bits &= !bit;
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+        removed
+    }
+
+    /// Returns true if `id` is contained in this set.
+    #[must_use]
+    pub fn contains(&self, id: ComponentId) -> bool {
+        let (word, bit) = Self::position(id);
+        self.words.get(word).map_or(false, |bits| bits & bit != 0)
+    }
+
+    /// Returns true if this set and `other` have at least one id in common.
+    #[must_use]
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.words.iter().zip(&other.words).any(|(a, b)| a & b != 0)
+    }
+
+    /// Returns true if every id in `other` is contained in this set.
+    #[must_use]
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.words.len() <= self.words.len()
+            && self
+                .words
+                .iter()
+                .zip(&other.words)
+                .all(|(a, b)| a & b == *b)
+    }
+
+    /// Returns the number of ids in this set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|bits| bits.count_ones() as usize)
+            .sum()
+    }
+
+    /// Returns true if this set contains no ids.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Removes all ids from this set.
+    pub fn clear(&mut self) {
+        self.words.clear();
+    }
+
+    /// Returns an iterator over the ids in this set, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        (0_u32..).zip(&self.words).flat_map(|(word, bits)| {
+            (0..64_u32)
+                .filter(move |bit| bits & (1 << bit) != 0)
+                .map(move |bit| ComponentId(word * 64 + bit))
+        })
+    }
+
+    /// Returns the ids of every registered name with the authority
+    /// `authority`.
+    pub(crate) fn for_authority(authority: &Identifier) -> Self {
+        registry()
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .by_authority
+            .get(authority)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Returns the authority of each registered name, paired with the ids of
+    /// the names in `self` that have it.
+    pub(crate) fn by_authority(&self) -> Vec<(Identifier, ComponentSet)> {
+        let registry = registry().read().unwrap_or_else(PoisonError::into_inner);
+        registry
+            .by_authority
+            .iter()
+            .filter_map(|(authority, ids)| {
+                let ids = self.intersection(ids);
+                (!ids.is_empty()).then(|| (authority.clone(), ids))
+            })
+            .collect()
+    }
+
+    fn intersection(&self, other: &Self) -> Self {
+        let mut words = self
+            .words
+            .iter()
+            .zip(&other.words)
+            .map(|(a, b)| a & b)
+            .collect::<Vec<_>>();
+        while words.last() == Some(&0) {
+            words.pop();
+        }
+        Self { words }
+    }
+
+    fn position(id: ComponentId) -> (usize, u64) {
+        (id.index() / 64, 1 << (id.index() % 64))
+    }
+}
+
+impl Debug for ComponentSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl FromIterator<ComponentId> for ComponentSet {
+    fn from_iter<T: IntoIterator<Item = ComponentId>>(iter: T) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<ComponentId> for ComponentSet {
+    fn extend<T: IntoIterator<Item = ComponentId>>(&mut self, iter: T) {
+        for id in iter {
+            self.insert(id);
+        }
+    }
+}
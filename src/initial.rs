@@ -0,0 +1,104 @@
+use kempt::Map;
+
+use crate::any::AnyComponent;
+use crate::components::DynamicComponent;
+use crate::{ComponentId, Name, StyleComponent};
+
+/// A registry of initial values for style components.
+///
+/// Initial values are used by [`Style::computed`](crate::Style::computed) to
+/// fill in components that a style does not contain, allowing renderers to
+/// rely on [`Style::get`](crate::Style::get) returning a value for every
+/// registered component.
+#[derive(Default, Clone)]
+pub struct InitialValues {
+    values: Map<ComponentId, AnyComponent>,
+}
+
+impl std::fmt::Debug for InitialValues {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.values.values()).finish()
+    }
+}
+
+impl InitialValues {
+    /// Returns an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the [initial value](StyleComponent::initial_value) of `T`.
+    /// Returns false if `T` does not provide an initial value.
+    pub fn register<T: StyleComponent + Clone>(&mut self) -> bool {
+        if let Some(initial) = T::initial_value() {
+            self.insert(initial);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Registers the [initial value](StyleComponent::initial_value) of `T`
+    /// and returns self.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `T` does not provide an initial value.
+    #[must_use]
+    pub fn registered<T: StyleComponent + Clone>(mut self) -> Self {
+        assert!(
+            self.register::<T>(),
+            "{} does not provide an initial value",
+            T::name()
+        );
+        self
+    }
+
+    /// Registers `initial` as the initial value of its component, replacing
+    /// any previously registered value.
+    pub fn insert<T: DynamicComponent + Clone>(&mut self, initial: T) {
+        let initial = AnyComponent::new(initial);
+        self.values.insert(initial.component_id(), initial);
+    }
+
+    /// Registers `initial` as the initial value of its component and returns
+    /// self.
+    #[must_use]
+    pub fn with<T: DynamicComponent + Clone>(mut self, initial: T) -> Self {
+        self.insert(initial);
+        self
+    }
+
+    /// Returns the registered initial value of `T`, if present.
+    #[must_use]
+    pub fn get<T: StyleComponent>(&self) -> Option<&T> {
+        self.values
+            .get(&T::component_id())
+            .and_then(AnyComponent::get)
+    }
+
+    /// Returns the registered initial value of the component named `name`, if
+    /// present.
+    #[must_use]
+    pub fn get_by_name(&self, name: &Name) -> Option<&AnyComponent> {
+        self.values.get(&ComponentId::find(name)?)
+    }
+
+    /// Returns the number of registered initial values.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns true if no initial values are registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns an iterator over the registered initial values.
+    pub fn iter(&self) -> impl Iterator<Item = &AnyComponent> + '_ {
+        self.values.values()
+    }
+}
@@ -2,8 +2,14 @@
 //! for Rust apps.
 //!
 //! This crate makes it easy to annotate types as [`StyleComponent`]s that can
-//! be used within a [`Style`]. This crate provides no [`StyleComponent`]
-//! implementors.
+//! be used within a [`Style`]. By default, this crate provides no
+//! [`StyleComponent`] implementors. Enabling the `standard` feature provides a
+//! library of common components in the [`standard`] module, and enabling the
+//! `color` feature provides color components in the [`color`] module.
+//!
+//! Enabling the `rayon` feature allows resolving a [`StyleTree`] in parallel
+//! using [`StyleTree::par_resolve`]. Enabling the `hot-reload` feature
+//! provides `StyleWatcher`, which reloads styles from files as they change.
 #![forbid(unsafe_code)]
 #![warn(
     clippy::cargo,
@@ -18,21 +24,62 @@
 )]
 #![cfg_attr(doc, warn(rustdoc::all))]
 
+mod aliases;
 mod any;
+#[cfg(feature = "color")]
+pub mod color;
 mod components;
+mod conversion;
+mod dynamic;
+mod history;
+mod ids;
+mod initial;
 mod names;
+mod observable;
+mod patch;
+mod query;
+#[cfg(feature = "hot-reload")]
+mod reload;
+mod scoped;
+#[cfg(feature = "standard")]
+pub mod standard;
 mod style;
+mod tree;
+mod units;
+mod validation;
+mod world;
 
+pub use aliases::DeprecationCallbackId;
 pub use any::AnyComponent;
+pub use history::{Checkpoint, StyleHistory};
+pub use ids::{ComponentId, ComponentSet};
+pub use initial::InitialValues;
 #[doc(hidden)]
 pub use names::IDENTIFIERS;
 pub use names::{Identifier, Name, NameKey, StaticName};
+pub use observable::{ChangeKind, ChangeQueue, ObservableStyle, StyleChange, SubscriptionId};
 #[cfg(feature = "derive")]
 pub use stylecs_macros::StyleComponent;
-pub use stylecs_shared::InvalidIdentifier;
+#[doc(hidden)]
+pub use stylecs_shared::validate_identifier_kind as __validate_identifier;
+pub use stylecs_shared::{InvalidIdentifier, InvalidIdentifierKind};
 
 pub use self::components::{DynamicComponent, StyleComponent};
-pub use self::style::{Iter, Style};
+pub use self::conversion::ConversionRegistry;
+pub use self::dynamic::{DynamicValue, MergeStrategy, ScriptValue};
+pub use self::patch::{Conflict, MergeConflict, Operation, PatchValue, StylePatch, ThreeWayMerge};
+pub use self::query::{Access, Query, QueryIter, QueryMut, ReadOnlyQuery};
+#[cfg(feature = "hot-reload")]
+pub use self::reload::{Reload, ReloadError, ReloadEvent, StyleWatcher, WatchHandle};
+pub use self::scoped::ScopedStyle;
+pub use self::style::{ByAuthority, ChangedSince, Generation, Iter, Keyword, Prefixed, Style};
+pub use self::tree::{NodeId, StyleTree};
+pub use self::units::{Axis, Dimension, FontSizeSource, ResolutionContext, Unit};
+pub use self::validation::{InvalidValue, Violation};
+pub use self::world::{Column, ColumnTypeMismatch, Entity, StyleWorld};
+
+/// The authority reserved for components provided by this crate.
+pub const AUTHORITY: &str = "stylecs";
 
 #[doc(hidden)]
 #[macro_export]
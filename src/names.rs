@@ -1,11 +1,13 @@
 use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::fmt::{Debug, Display};
+use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
 
 use interner::global::{GlobalString, StaticPooledString, StringPool};
-use stylecs_shared::InvalidIdentifier;
+use stylecs_shared::{InvalidIdentifier, InvalidIdentifierKind};
 
 /// A name that contains only `a-z`, `A-Z`, `0-9`, or `_` characters.
 #[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Clone)]
@@ -26,8 +28,8 @@ impl Identifier {
     ///
     /// # Errors
     ///
-    /// Returns [`InvalidIdentifier`] if `name` contains any character that is
-    /// not one of:
+    /// Returns [`InvalidIdentifier`] if `name` is empty or contains any
+    /// character that is not one of:
     ///
     /// - `a-z`
     /// - `A-Z`
@@ -35,7 +37,10 @@ impl Identifier {
     /// - `_`
     pub fn new<'a>(name: impl Into<Cow<'a, str>>) -> Result<Self, InvalidIdentifier> {
         let name = name.into();
-        stylecs_shared::validate_identifier(&name).map(|_| Self(IDENTIFIERS.get(name)))
+        match stylecs_shared::validate_identifier_kind(&name) {
+            Ok(()) => Ok(Self(IDENTIFIERS.get(name))),
+            Err(kind) => Err(InvalidIdentifier::new(name, kind)),
+        }
     }
 
     /// Validates `name` and returns an error if any invalid characters are
@@ -43,13 +48,17 @@ impl Identifier {
     ///
     /// # Errors
     ///
-    /// Returns [`InvalidIdentifier`] if `name` contains any character that is
-    /// not one of:
+    /// Returns [`InvalidIdentifier`] if `name` is empty or contains any
+    /// character that is not one of:
     ///
     /// - `a-z`
     /// - `A-Z`
     /// - `0-9`
     /// - `_`
+    ///
+    /// Because this function is `const`, the returned error's
+    /// [`input`](InvalidIdentifier::input) is empty. [`Identifier::new`]
+    /// returns an error that includes the input.
     pub const fn validate(name: &str) -> Result<(), InvalidIdentifier> {
         stylecs_shared::validate_identifier(name)
     }
@@ -97,8 +106,10 @@ impl Deref for Identifier {
 
 /// A globally unique name.
 ///
-/// This structure has an [`authority`](Self::authority) and a [`name`](Self::name).
-#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone)]
+/// This structure has an [`authority`](Self::authority), an optional
+/// [`namespace`](Self::namespace), and a [`name`](Self::name). When displayed,
+/// each segment is separated by `::`, e.g., `gooey::button::padding`.
+#[derive(Debug, Clone)]
 pub struct Name {
     /// The unique name of the source of this name. For example, this could be
     /// the name of the crate it was defined within.
@@ -112,6 +123,30 @@ pub struct Name {
     /// two authorities can define their own `color` components without
     /// conflicts.
     pub name: Identifier,
+    /// The segments between the authority and the name.
+    ///
+    /// This is `None` for names without a namespace, keeping two-segment
+    /// names free of additional allocations. An empty namespace is considered
+    /// equal to `None`.
+    namespace: Option<Arc<[Identifier]>>,
+}
+
+impl Eq for Name {}
+
+impl PartialEq for Name {
+    fn eq(&self, other: &Self) -> bool {
+        self.authority == other.authority
+            && self.name == other.name
+            && self.namespace() == other.namespace()
+    }
+}
+
+impl Hash for Name {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.authority.hash(state);
+        self.namespace().hash(state);
+        self.name.hash(state);
+    }
 }
 
 impl Name {
@@ -142,8 +177,194 @@ impl Name {
         Ok(Self {
             authority: authority.into_identifier()?,
             name: name.into_identifier()?,
+            namespace: None,
+        })
+    }
+
+    /// Returns a new [`Name`] using `authority` and a path of `segments`. The
+    /// final segment is used as the [`name`](Self::name), and all preceding
+    /// segments become the [`namespace`](Self::namespace).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidIdentifier`] if any invalid [`Identifier`] characters
+    /// are encountered or if `segments` is empty.
+    pub fn with_path<I>(
+        authority: impl Identifiable,
+        segments: impl IntoIterator<Item = I>,
+    ) -> Result<Self, InvalidIdentifier>
+    where
+        I: Identifiable,
+    {
+        let authority = authority.into_identifier()?;
+        let mut namespace = segments
+            .into_iter()
+            .map(Identifiable::into_identifier)
+            .collect::<Result<Vec<_>, _>>()?;
+        let name = namespace.pop().ok_or_else(|| {
+            InvalidIdentifier::new("", InvalidIdentifierKind::Empty { offset: 0 })
+        })?;
+        Ok(Self {
+            authority,
+            name,
+            namespace: (!namespace.is_empty()).then(|| Arc::from(namespace)),
+        })
+    }
+
+    /// Returns the segments between the [`authority`](Self::authority) and the
+    /// [`name`](Self::name).
+    #[must_use]
+    pub fn namespace(&self) -> &[Identifier] {
+        self.namespace.as_deref().unwrap_or_default()
+    }
+
+    /// Returns an iterator over the segments of this name after the
+    /// [`authority`](Self::authority), ending with the [`name`](Self::name).
+    pub fn segments(&self) -> impl Iterator<Item = &Identifier> + '_ {
+        self.namespace().iter().chain(std::iter::once(&self.name))
+    }
+
+    /// Returns a new name with `segment` appended to this name's path. This
+    /// name's [`name`](Self::name) becomes the final segment of the returned
+    /// name's [`namespace`](Self::namespace).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidIdentifier`] if any invalid [`Identifier`] characters
+    /// are encountered.
+    pub fn join(&self, segment: impl Identifiable) -> Result<Self, InvalidIdentifier> {
+        Ok(Self {
+            authority: self.authority.clone(),
+            name: segment.into_identifier()?,
+            namespace: Some(self.segments().cloned().collect()),
         })
     }
+
+    /// Returns true if `prefix` has the same authority as this name and all of
+    /// its segments match the leading segments of this name. A name is
+    /// considered to start with itself.
+    #[must_use]
+    pub fn starts_with(&self, prefix: &Name) -> bool {
+        let prefix_len = prefix.namespace().len() + 1;
+        self.authority == prefix.authority
+            && self.namespace().len() + 1 >= prefix_len
+            && self.segments().zip(prefix.segments()).all(|(a, b)| a == b)
+    }
+
+    /// Returns this name formatted using kebab-case, as is common in CSS.
+    ///
+    /// Segments are separated by `:` instead of `::`, and underscores within
+    /// each segment are replaced with hyphens. For example,
+    /// `gooey::padding_left` becomes `gooey:padding-left`. The result can be
+    /// parsed using [`Name::from_kebab_case`].
+    #[must_use]
+    pub fn to_kebab_case(&self) -> String {
+        let mut kebab = String::new();
+        if PRIVATE != self.authority.0 || !self.namespace().is_empty() {
+            kebab.push_str(&self.authority.replace('_', "-"));
+            kebab.push(':');
+        }
+        for segment in self.namespace() {
+            kebab.push_str(&segment.replace('_', "-"));
+            kebab.push(':');
+        }
+        kebab.push_str(&self.name.replace('_', "-"));
+        kebab
+    }
+
+    /// Parses a name formatted by [`Name::to_kebab_case`], returning the name
+    /// using the same [`Identifier`]s as its snake-case form.
+    ///
+    /// If the parsed name is an [alias](Name::register_alias), its canonical
+    /// name is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidIdentifier`] if any segment is empty or contains a
+    /// character other than `a-z`, `A-Z`, `0-9`, or `-`. The error's offset
+    /// is relative to `kebab`.
+    pub fn from_kebab_case(kebab: &str) -> Result<Self, InvalidIdentifier> {
+        if let Some(offset) = kebab.find('_') {
+            // An underscore would be silently accepted after converting the
+            // segment to snake case.
+            return Err(InvalidIdentifier::new(
+                kebab,
+                InvalidIdentifierKind::InvalidCharacter {
+                    offset,
+                    character: '_',
+                },
+            ));
+        }
+        Self::parse_path(kebab, ":", |segment| Cow::Owned(segment.replace('-', "_")))
+            .map(Name::resolve_alias)
+    }
+
+    /// Parses `input` as a path of segments separated by `separator`, using
+    /// `to_identifier` to convert each segment into a valid identifier. When
+    /// more than one segment is present, the first segment is the authority.
+    ///
+    /// `to_identifier` must preserve the byte offsets of each segment so that
+    /// errors can be reported relative to `input`.
+    fn parse_path<'a>(
+        input: &'a str,
+        separator: &str,
+        to_identifier: impl Fn(&'a str) -> Cow<'a, str>,
+    ) -> Result<Self, InvalidIdentifier> {
+        let mut segments = Vec::new();
+        let mut offset = 0;
+        for segment in input.split(separator) {
+            let identifier = Identifier::new(to_identifier(segment)).map_err(|err| {
+                // Report the error relative to the entire input.
+                let kind = match err.kind {
+                    InvalidIdentifierKind::InvalidCharacter {
+                        offset: relative,
+                        character,
+                    } => InvalidIdentifierKind::InvalidCharacter {
+                        offset: offset + relative,
+                        character,
+                    },
+                    InvalidIdentifierKind::Empty { offset: relative } => {
+                        InvalidIdentifierKind::Empty {
+                            offset: offset + relative,
+                        }
+                    }
+                };
+                InvalidIdentifier::new(input, kind)
+            })?;
+            segments.push(identifier);
+            offset += segment.len() + separator.len();
+        }
+
+        let name = segments.pop().expect("split always returns a segment");
+        if segments.is_empty() {
+            Ok(Self {
+                authority: Identifier::private(),
+                name,
+                namespace: None,
+            })
+        } else {
+            let authority = segments.remove(0);
+            Ok(Self {
+                authority,
+                name,
+                namespace: (!segments.is_empty()).then(|| Arc::from(segments)),
+            })
+        }
+    }
+}
+
+impl Ord for Name {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.authority
+            .cmp(&other.authority)
+            .then_with(|| self.segments().cmp(other.segments()))
+    }
+}
+
+impl PartialOrd for Name {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 impl<'a> From<Name> for Cow<'a, Name> {
@@ -160,29 +381,28 @@ impl<'a> From<&'a Name> for Cow<'a, Name> {
 
 impl Display for Name {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if PRIVATE != self.authority.0 {
+        // The private authority can only be omitted when it would not be
+        // ambiguous with a namespaced name.
+        if PRIVATE != self.authority.0 || !self.namespace().is_empty() {
             f.write_str(&self.authority)?;
             f.write_str("::")?;
         }
+        for segment in self.namespace() {
+            f.write_str(segment)?;
+            f.write_str("::")?;
+        }
         f.write_str(&self.name)
     }
 }
 
+/// Parses a name formatted by [`Name`]'s `Display` implementation. If the
+/// parsed name is an [alias](Name::register_alias), its canonical name is
+/// returned.
 impl FromStr for Name {
     type Err = InvalidIdentifier;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Some((authority, name)) = s.split_once("::") {
-            let authority = Identifier::new(authority)?;
-            let name = Identifier::new(name)?;
-            Ok(Self { authority, name })
-        } else {
-            let name = Identifier::new(s)?;
-            Ok(Self {
-                authority: Identifier::private(),
-                name,
-            })
-        }
+        Self::parse_path(s, "::", Cow::Borrowed).map(Name::resolve_alias)
     }
 }
 
@@ -199,6 +419,130 @@ fn name_strings() {
     assert_eq!(qualified_string, "authority::name");
     let parsed: Name = qualified_string.parse().unwrap();
     assert_eq!(parsed, qualified);
+
+    let nested: Name = "gooey::button::padding".parse().unwrap();
+    assert_eq!(nested.authority, "gooey");
+    assert_eq!(nested.namespace(), &[Identifier::new("button").unwrap()]);
+    assert_eq!(nested.name, "padding");
+    assert_eq!(nested.to_string(), "gooey::button::padding");
+    assert_eq!(
+        nested,
+        Name::with_path("gooey", ["button", "padding"]).unwrap()
+    );
+    assert_eq!(
+        nested,
+        Name::new("gooey", "button")
+            .unwrap()
+            .join("padding")
+            .unwrap()
+    );
+
+    let private_nested = Name::with_path(Identifier::private(), ["a", "b"]).unwrap();
+    assert_eq!(private_nested.to_string(), "_::a::b");
+    assert_eq!(
+        private_nested.to_string().parse::<Name>().unwrap(),
+        private_nested
+    );
+
+    assert!(Name::with_path("gooey", Vec::<&str>::new()).is_err());
+
+    // An empty namespace is equivalent to no namespace.
+    let literal = Name {
+        authority: Identifier::new("authority").unwrap(),
+        name: Identifier::new("name").unwrap(),
+        namespace: Some(Arc::from([])),
+    };
+    assert_eq!(literal, qualified);
+    assert_eq!(literal.to_string(), "authority::name");
+
+    let err = "gooey::button::".parse::<Name>().unwrap_err();
+    assert_eq!(err.kind, InvalidIdentifierKind::Empty { offset: 15 });
+    assert_eq!(
+        err.to_string(),
+        "empty identifier at byte 15 in \"gooey::button::\""
+    );
+    assert_eq!(err.input, "gooey::button::");
+    let err = "gooey::but-ton::padding".parse::<Name>().unwrap_err();
+    assert_eq!(
+        err.kind,
+        InvalidIdentifierKind::InvalidCharacter {
+            offset: 10,
+            character: '-'
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "invalid character '-' at byte 10 in identifier \"gooey::but-ton::padding\""
+    );
+}
+
+#[test]
+fn kebab_names() {
+    let qualified = Name::new("gooey", "padding_left").unwrap();
+    assert_eq!(qualified.to_kebab_case(), "gooey:padding-left");
+    let parsed = Name::from_kebab_case("gooey:padding-left").unwrap();
+    assert_eq!(parsed, qualified);
+    // Parsing reuses the interned identifiers of the snake-case form.
+    assert_eq!(parsed.name.as_ptr(), qualified.name.as_ptr());
+
+    let private = Name::private("font_size").unwrap();
+    assert_eq!(private.to_kebab_case(), "font-size");
+    assert_eq!(Name::from_kebab_case("font-size").unwrap(), private);
+
+    let nested = Name::with_path("my_app", ["tool_bar", "corner_radius"]).unwrap();
+    assert_eq!(nested.to_kebab_case(), "my-app:tool-bar:corner-radius");
+    assert_eq!(
+        Name::from_kebab_case(&nested.to_kebab_case()).unwrap(),
+        nested
+    );
+
+    let err = Name::from_kebab_case("gooey:padding_left").unwrap_err();
+    assert_eq!(
+        err.kind,
+        InvalidIdentifierKind::InvalidCharacter {
+            offset: 13,
+            character: '_'
+        }
+    );
+    let err = Name::from_kebab_case("gooey:padding.left").unwrap_err();
+    assert_eq!(
+        err.kind,
+        InvalidIdentifierKind::InvalidCharacter {
+            offset: 13,
+            character: '.'
+        }
+    );
+    assert_eq!(
+        Name::from_kebab_case("gooey:").unwrap_err().kind,
+        InvalidIdentifierKind::Empty { offset: 6 }
+    );
+}
+
+#[test]
+fn name_paths() {
+    let button = Name::new("gooey", "button").unwrap();
+    let padding: Name = "gooey::button::padding".parse().unwrap();
+    let deep: Name = "gooey::button::padding::left".parse().unwrap();
+    assert!(padding.starts_with(&button));
+    assert!(deep.starts_with(&button));
+    assert!(deep.starts_with(&padding));
+    assert!(button.starts_with(&button));
+    assert!(!button.starts_with(&padding));
+    assert!(!padding.starts_with(&Name::new("other", "button").unwrap()));
+    assert!(!padding.starts_with(&Name::new("gooey", "label").unwrap()));
+
+    // Names sort by their segments, keeping names under a prefix together.
+    let mut names = vec![
+        Name::new("gooey", "label").unwrap(),
+        deep.clone(),
+        button.clone(),
+        padding.clone(),
+    ];
+    names.sort();
+    assert_eq!(
+        names,
+        [button, padding, deep, Name::new("gooey", "label").unwrap()]
+    );
 }
 
 pub trait Identifiable {
@@ -227,6 +571,10 @@ impl Identifiable for Identifier {
 ///
 /// This allows a minor optimization such that [`Identifier`]s created always
 /// exist.
+///
+/// Names with a [namespace](Name::namespace) can be defined by passing the
+/// namespace's segments as an array between the authority and the name:
+/// `static_name!("gooey", ["button"], "padding")`.
 #[macro_export]
 macro_rules! static_name {
     ($private_name:expr) => {
@@ -234,16 +582,28 @@ macro_rules! static_name {
     };
     ($authority:expr, $name:expr) => {
         $crate::StaticName::new(
-            match $crate::Identifier::validate($authority) {
+            match $crate::__validate_identifier($authority) {
                 Ok(_) => $crate::IDENTIFIERS.get_static($authority),
+                Err($crate::InvalidIdentifierKind::Empty { .. }) => panic!("authority is empty"),
                 Err(_) => panic!("invalid character in authority"),
             },
-            match $crate::Identifier::validate($name) {
+            match $crate::__validate_identifier($name) {
                 Ok(_) => $crate::IDENTIFIERS.get_static($name),
+                Err($crate::InvalidIdentifierKind::Empty { .. }) => panic!("name is empty"),
                 Err(_) => panic!("invalid character in name"),
             },
         )
     };
+    ($authority:expr, [$($segment:expr),+ $(,)?], $name:expr) => {{
+        const NAMESPACE: &[&str] = &[$(
+            match $crate::__validate_identifier($segment) {
+                Ok(_) => $segment,
+                Err($crate::InvalidIdentifierKind::Empty { .. }) => panic!("namespace segment is empty"),
+                Err(_) => panic!("invalid character in namespace"),
+            }
+        ),+];
+        $crate::StaticName::with_namespace($crate::static_name!($authority, $name), NAMESPACE)
+    }};
 }
 
 /// A statically defined [`Name`].
@@ -257,6 +617,18 @@ macro_rules! static_name {
 /// assert_eq!(PRIVATE.to_name(), Name::private("private").unwrap());
 /// ```
 ///
+/// # Creating a static namespaced name
+///
+/// ```rust
+/// use stylecs::{static_name, Name, StaticName};
+///
+/// static PADDING: StaticName = static_name!("gooey", ["button"], "padding");
+/// assert_eq!(
+///     PADDING.to_name(),
+///     Name::with_path("gooey", ["button", "padding"]).unwrap()
+/// );
+/// ```
+///
 /// # Why use [`StaticName`]?
 ///
 /// This type enables a minor optimization. Each [`Identifier`] guarantees that
@@ -271,12 +643,26 @@ macro_rules! static_name {
 pub struct StaticName {
     authority: StaticPooledString,
     name: StaticPooledString,
+    namespace: &'static [&'static str],
+    identifiers: OnceLock<Arc<[Identifier]>>,
 }
 
 impl StaticName {
     #[doc(hidden)]
     pub const fn new(authority: StaticPooledString, name: StaticPooledString) -> Self {
-        Self { authority, name }
+        Self {
+            authority,
+            name,
+            namespace: &[],
+            identifiers: OnceLock::new(),
+        }
+    }
+
+    #[doc(hidden)]
+    #[must_use]
+    pub const fn with_namespace(mut self, namespace: &'static [&'static str]) -> Self {
+        self.namespace = namespace;
+        self
     }
 
     /// Returns this static instance as a regular [`Name`].
@@ -290,6 +676,18 @@ impl<'a> From<&'a StaticName> for Name {
         Self {
             authority: Identifier(value.authority.get().clone()),
             name: Identifier(value.name.get().clone()),
+            namespace: (!value.namespace.is_empty()).then(|| {
+                value
+                    .identifiers
+                    .get_or_init(|| {
+                        value
+                            .namespace
+                            .iter()
+                            .map(|segment| Identifier(IDENTIFIERS.get(*segment)))
+                            .collect()
+                    })
+                    .clone()
+            }),
         }
     }
 }
@@ -344,10 +742,16 @@ impl<'a> Ord for NameKey<'a> {
         // shouldn't conflict.
         let a = &**self;
         let b = &**other;
-        match a.name.as_ptr().cmp(&b.name.as_ptr()) {
-            order @ (Ordering::Greater | Ordering::Less) => order,
-            Ordering::Equal => a.authority.as_ptr().cmp(&b.authority.as_ptr()),
-        }
+        a.name
+            .as_ptr()
+            .cmp(&b.name.as_ptr())
+            .then_with(|| a.authority.as_ptr().cmp(&b.authority.as_ptr()))
+            .then_with(|| {
+                a.namespace()
+                    .iter()
+                    .map(|segment| segment.as_ptr())
+                    .cmp(b.namespace().iter().map(|segment| segment.as_ptr()))
+            })
     }
 }
 
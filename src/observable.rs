@@ -0,0 +1,398 @@
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, Weak};
+
+use alot::{LotId, Lots};
+
+use crate::any::AnyComponent;
+use crate::components::DynamicComponent;
+use crate::{Name, PatchValue, Style, StyleComponent, StylePatch};
+
+/// A shared [`Style`] that notifies subscribers when its components change.
+///
+/// Cloning an `ObservableStyle` produces another handle to the same style.
+/// Subscribers can be notified through callbacks or by draining a
+/// [`ChangeQueue`]. Callbacks are invoked after the style has been updated and
+/// its lock has been released, allowing callbacks to read from the style.
+#[derive(Clone)]
+pub struct ObservableStyle(Arc<Mutex<Data>>);
+
+struct Data {
+    style: Style,
+    subscribers: Lots<Subscriber>,
+}
+
+struct Subscriber {
+    name: Name,
+    target: Target,
+}
+
+enum Target {
+    Callback(Arc<dyn Fn(&StyleChange) + Send + Sync>),
+    Queue(Arc<Mutex<VecDeque<StyleChange>>>),
+}
+
+impl Default for ObservableStyle {
+    fn default() -> Self {
+        Self::new(Style::new())
+    }
+}
+
+impl Debug for ObservableStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ObservableStyle")
+            .field(&self.data().style)
+            .finish()
+    }
+}
+
+#[allow(clippy::must_use_candidate)]
+impl ObservableStyle {
+    /// Returns a new observable style containing `style`.
+    #[must_use]
+    pub fn new(style: Style) -> Self {
+        Self(Arc::new(Mutex::new(Data {
+            style,
+            subscribers: Lots::new(),
+        })))
+    }
+
+    fn data(&self) -> MutexGuard<'_, Data> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns a copy of the current style.
+    #[must_use]
+    pub fn snapshot(&self) -> Style {
+        self.data().style.clone()
+    }
+
+    /// Invokes `map` with a reference to the current style and returns the
+    /// result.
+    ///
+    /// The style is locked while `map` executes. Modifying this style from
+    /// within `map` will deadlock.
+    pub fn map<R>(&self, map: impl FnOnce(&Style) -> R) -> R {
+        map(&self.data().style)
+    }
+
+    /// Returns a clone of the style component of type `T`, if present.
+    #[must_use]
+    pub fn get<T: StyleComponent + Clone>(&self) -> Option<T> {
+        self.data().style.get::<T>().cloned()
+    }
+
+    /// Adds a component to this style, replacing any existing value of the
+    /// same type, and notifies subscribers of the change. Subscribers are not
+    /// notified if the component replaced an equivalent value.
+    pub fn push<T: DynamicComponent + Clone>(&self, component: T) {
+        let mut changed = AnyComponent::new(component);
+        changed.canonicalize_name();
+        let name = changed.name();
+        let mut data = self.data();
+        let kind = match data.style.get_by_name(&name) {
+            Some(previous) if previous.equivalent(&changed) => None,
+            Some(_) => Some(ChangeKind::Replaced),
+            None if data.style.keyword(&name).is_some() => Some(ChangeKind::Replaced),
+            None => Some(ChangeKind::Inserted),
+        };
+        data.style.push_any(changed.clone());
+        let Some(kind) = kind else {
+            return;
+        };
+        notify(
+            data,
+            &[StyleChange {
+                name,
+                kind,
+                component: Some(changed),
+            }],
+        );
+    }
+
+    /// Removes and returns the style component of type `T`, notifying
+    /// subscribers if a component or [`Keyword`](crate::Keyword) was removed.
+    pub fn remove<T: StyleComponent>(&self) -> Option<T> {
+        let name = T::name();
+        let mut data = self.data();
+        let had_keyword = data.style.keyword(&name).is_some();
+        let removed = data.style.remove::<T>();
+        if removed.is_some() || had_keyword {
+            notify_removed(data, name);
+        }
+        removed
+    }
+
+    /// Removes and returns the style component named `name`, notifying
+    /// subscribers if a component or [`Keyword`](crate::Keyword) was removed.
+    pub fn remove_by_name(&self, name: &Name) -> Option<AnyComponent> {
+        let mut data = self.data();
+        let had_keyword = data.style.keyword(name).is_some();
+        let removed = data.style.remove_by_name(name);
+        if removed.is_some() || had_keyword {
+            notify_removed(data, name.clone());
+        }
+        removed
+    }
+
+    /// Merges `other` into this style using the same rules as
+    /// [`Style::merged_with`], notifying subscribers of each component that
+    /// was inserted or altered by the merge.
+    pub fn merge(&self, other: &Style) {
+        let mut data = self.data();
+        let previous = other
+            .iter()
+            .map(|component| {
+                let name = component.name();
+                let before = data.style.get_by_name(&name).cloned();
+                (name, before)
+            })
+            .collect::<Vec<_>>();
+        let style = std::mem::take(&mut data.style);
+        data.style = style.merged_with(other);
+        let changes = previous
+            .into_iter()
+            .filter_map(|(name, before)| {
+                let after = data.style.get_by_name(&name)?;
+                let kind = match before {
+                    Some(before) if before.equivalent(after) => return None,
+                    Some(_) => ChangeKind::Merged,
+                    None => ChangeKind::Inserted,
+                };
+                Some(StyleChange {
+                    component: Some(after.clone()),
+                    name,
+                    kind,
+                })
+            })
+            .collect::<Vec<_>>();
+        notify(data, &changes);
+    }
+
+    /// Replaces the contents of this style with `style`, returning the
+    /// previous style. Subscribers are notified of every component or
+    /// [`Keyword`](crate::Keyword) that was inserted, replaced with a
+    /// different value, or removed.
+    pub fn replace(&self, style: Style) -> Style {
+        self.replace_with_diff(style).0
+    }
+
+    /// Replaces the contents of this style with `style`, returning the
+    /// previous style and the [`StylePatch`] describing the changes
+    /// subscribers were notified of.
+    pub(crate) fn replace_with_diff(&self, style: Style) -> (Style, StylePatch) {
+        let mut data = self.data();
+        let patch = StylePatch::diff(&data.style, &style);
+        let changes = patch
+            .operations()
+            .iter()
+            .map(|operation| StyleChange {
+                name: operation.name.clone(),
+                kind: match (&operation.before, &operation.after) {
+                    (None, _) => ChangeKind::Inserted,
+                    (Some(_), None) => ChangeKind::Removed,
+                    (Some(_), Some(_)) => ChangeKind::Replaced,
+                },
+                component: operation
+                    .after
+                    .as_ref()
+                    .and_then(PatchValue::as_component)
+                    .cloned(),
+            })
+            .collect::<Vec<_>>();
+        let previous = std::mem::replace(&mut data.style, style);
+        notify(data, &changes);
+        (previous, patch)
+    }
+
+    /// Invokes `callback` each time the component of type `T` changes.
+    pub fn on_change<T: StyleComponent>(
+        &self,
+        callback: impl Fn(&StyleChange) + Send + Sync + 'static,
+    ) -> SubscriptionId {
+        self.on_change_by_name(T::name(), callback)
+    }
+
+    /// Invokes `callback` each time the component named `name` changes.
+    ///
+    /// If `name` is an [alias](Name::register_alias), changes to its
+    /// canonical name are observed.
+    pub fn on_change_by_name(
+        &self,
+        name: Name,
+        callback: impl Fn(&StyleChange) + Send + Sync + 'static,
+    ) -> SubscriptionId {
+        SubscriptionId(self.data().subscribers.push(Subscriber {
+            name: name.into_canonical(),
+            target: Target::Callback(Arc::new(callback)),
+        }))
+    }
+
+    /// Returns a queue that receives each change to the component of type
+    /// `T`.
+    #[must_use]
+    pub fn subscribe<T: StyleComponent>(&self) -> ChangeQueue {
+        self.subscribe_by_name(T::name())
+    }
+
+    /// Returns a queue that receives each change to the component named
+    /// `name`.
+    ///
+    /// If `name` is an [alias](Name::register_alias), changes to its
+    /// canonical name are observed. The subscription is automatically removed
+    /// once all clones of the returned queue have been dropped.
+    #[must_use]
+    pub fn subscribe_by_name(&self, name: Name) -> ChangeQueue {
+        let changes = Arc::new(Mutex::new(VecDeque::new()));
+        let id = SubscriptionId(self.data().subscribers.push(Subscriber {
+            name: name.into_canonical(),
+            target: Target::Queue(changes.clone()),
+        }));
+        ChangeQueue(Arc::new(QueueHandle {
+            id,
+            changes,
+            style: Arc::downgrade(&self.0),
+        }))
+    }
+
+    /// Removes a subscription. Returns true if the subscription was found.
+    pub fn unsubscribe(&self, id: SubscriptionId) -> bool {
+        self.data().subscribers.remove(id.0).is_some()
+    }
+}
+
+impl From<Style> for ObservableStyle {
+    fn from(style: Style) -> Self {
+        Self::new(style)
+    }
+}
+
+/// Delivers `changes` to all interested subscribers. Queued changes are
+/// delivered immediately, while callbacks are invoked after `data` has been
+/// unlocked.
+fn notify(data: MutexGuard<'_, Data>, changes: &[StyleChange]) {
+    let mut callbacks = Vec::new();
+    for subscriber in data.subscribers.iter() {
+        for change in changes
+            .iter()
+            .filter(|change| change.name == subscriber.name)
+        {
+            match &subscriber.target {
+                Target::Callback(callback) => {
+                    callbacks.push((callback.clone(), change));
+                }
+                Target::Queue(queue) => {
+                    queue
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .push_back(change.clone());
+                }
+            }
+        }
+    }
+    drop(data);
+
+    for (callback, change) in callbacks {
+        callback(change);
+    }
+}
+
+/// Notifies subscribers that the component named `name` was removed.
+fn notify_removed(data: MutexGuard<'_, Data>, name: Name) {
+    notify(
+        data,
+        &[StyleChange {
+            name,
+            kind: ChangeKind::Removed,
+            component: None,
+        }],
+    );
+}
+
+/// A handle to a subscription created by [`ObservableStyle`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct SubscriptionId(LotId);
+
+/// A queue of [`StyleChange`]s for a subscription to an [`ObservableStyle`].
+///
+/// Cloning a `ChangeQueue` produces another handle to the same queue. The
+/// subscription is removed when the last handle is dropped.
+#[derive(Debug, Clone)]
+pub struct ChangeQueue(Arc<QueueHandle>);
+
+#[derive(Debug)]
+struct QueueHandle {
+    id: SubscriptionId,
+    changes: Arc<Mutex<VecDeque<StyleChange>>>,
+    style: Weak<Mutex<Data>>,
+}
+
+impl Drop for QueueHandle {
+    fn drop(&mut self) {
+        if let Some(data) = self.style.upgrade() {
+            data.lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .subscribers
+                .remove(self.id.0);
+        }
+    }
+}
+
+impl ChangeQueue {
+    /// Returns the id of this subscription.
+    #[must_use]
+    pub fn id(&self) -> SubscriptionId {
+        self.0.id
+    }
+
+    fn changes(&self) -> MutexGuard<'_, VecDeque<StyleChange>> {
+        self.0
+            .changes
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the oldest change that has not been received yet.
+    #[must_use]
+    pub fn next(&self) -> Option<StyleChange> {
+        self.changes().pop_front()
+    }
+
+    /// Returns all changes that have not been received yet, oldest first.
+    #[must_use]
+    pub fn drain(&self) -> Vec<StyleChange> {
+        self.changes().drain(..).collect()
+    }
+
+    /// Returns true if there are no pending changes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changes().is_empty()
+    }
+}
+
+/// A change to a component in an [`ObservableStyle`].
+#[derive(Debug, Clone)]
+pub struct StyleChange {
+    /// The name of the component that changed.
+    pub name: Name,
+    /// The kind of change that happened.
+    pub kind: ChangeKind,
+    /// The value of the component after the change. This is `None` when the
+    /// component was removed.
+    pub component: Option<AnyComponent>,
+}
+
+/// The kind of a [`StyleChange`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ChangeKind {
+    /// The component was not previously present.
+    Inserted,
+    /// The component replaced a previous value.
+    Replaced,
+    /// The component was merged with another value using
+    /// [`DynamicComponent::merge`].
+    Merged,
+    /// The component was removed.
+    Removed,
+}
@@ -0,0 +1,309 @@
+use std::fmt::Debug;
+
+use crate::any::AnyComponent;
+use crate::components::DynamicComponent;
+use crate::style::Value;
+use crate::{ComponentId, Keyword, Name, Style, StyleComponent};
+
+/// A list of changes to a [`Style`], keyed by component [`Name`].
+///
+/// Patches allow sending changes to a style rather than the entire style. A
+/// patch can be computed with [`StylePatch::diff`], applied with
+/// [`StylePatch::apply`], inverted with [`StylePatch::inverted`] and combined
+/// with [`StylePatch::composed_with`].
+///
+/// Each [`Operation`] records the value it expects to replace. Applying a
+/// patch to a style whose values no longer match these expectations results
+/// in a [`Conflict`] rather than silently overwriting the other changes.
+/// Values are compared using [`DynamicComponent::equivalent`], so components
+/// that do not implement it are always considered changed.
+#[derive(Debug, Clone, Default)]
+pub struct StylePatch {
+    operations: Vec<Operation>,
+}
+
+/// A single change within a [`StylePatch`].
+///
+/// The operation represented depends on `after`:
+///
+/// - `Some(PatchValue::component(..))`: sets the component.
+/// - `Some(PatchValue::keyword::<T>(..))`: sets a [`Keyword`].
+/// - `None`: removes the component or keyword.
+#[derive(Debug, Clone)]
+pub struct Operation {
+    /// The name of the component being changed.
+    pub name: Name,
+    /// The value expected to be in the style before this operation is
+    /// applied.
+    pub before: Option<PatchValue>,
+    /// The value stored in the style after this operation is applied.
+    pub after: Option<PatchValue>,
+}
+
+/// A value stored in a [`Style`] for a component: either the component itself
+/// or a [`Keyword`].
+#[derive(Clone)]
+pub struct PatchValue(pub(crate) Value);
+
+/// A [`StylePatch`] operation whose expected value did not match the value
+/// found.
+#[derive(Debug, Clone)]
+pub struct Conflict {
+    /// The name of the conflicting component.
+    pub name: Name,
+    /// The value the operation expected.
+    pub expected: Option<PatchValue>,
+    /// The value that was found.
+    pub found: Option<PatchValue>,
+}
+
+/// The result of [`Style::three_way_merge`].
+#[derive(Debug, Clone)]
+pub struct ThreeWayMerge {
+    /// The merged style. Conflicting components contain the value from
+    /// `ours`.
+    pub style: Style,
+    /// The components that were changed differently by both sides.
+    pub conflicts: Vec<MergeConflict>,
+}
+
+/// A component that was changed differently by both sides of a
+/// [`Style::three_way_merge`].
+#[derive(Debug, Clone)]
+pub struct MergeConflict {
+    /// The name of the conflicting component.
+    pub name: Name,
+    /// The value in the common ancestor.
+    pub base: Option<PatchValue>,
+    /// The value in `ours`.
+    pub ours: Option<PatchValue>,
+    /// The value in `theirs`.
+    pub theirs: Option<PatchValue>,
+}
+
+impl StylePatch {
+    /// Returns an empty patch.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            operations: Vec::new(),
+        }
+    }
+
+    /// Returns a patch that transforms `from` into `to`.
+    #[must_use]
+    pub fn diff(from: &Style, to: &Style) -> Self {
+        let mut ids = from.ids().chain(to.ids()).collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids.dedup();
+        let operations = ids
+            .into_iter()
+            .filter_map(|id| {
+                let before = from.value(id).cloned().map(PatchValue);
+                let after = to.value(id).cloned().map(PatchValue);
+                (before != after).then(|| Operation {
+                    name: id.name(),
+                    before,
+                    after,
+                })
+            })
+            .collect();
+        Self { operations }
+    }
+
+    /// Adds `operation` to the end of this patch.
+    pub fn push(&mut self, operation: Operation) {
+        self.operations.push(operation);
+    }
+
+    /// Adds `operation` to the end of this patch and returns it.
+    #[must_use]
+    pub fn with(mut self, operation: Operation) -> Self {
+        self.push(operation);
+        self
+    }
+
+    /// Returns the operations in this patch.
+    #[must_use]
+    pub fn operations(&self) -> &[Operation] {
+        &self.operations
+    }
+
+    /// Returns the number of operations in this patch.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    /// Returns true if this patch contains no operations.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Applies each operation in this patch to `style`.
+    ///
+    /// # Errors
+    ///
+    /// Returns every [`Conflict`] found if the values in `style` do not match
+    /// the values the operations expected. `style` is not modified when an
+    /// error is returned.
+    pub fn apply(&self, style: &mut Style) -> Result<(), Vec<Conflict>> {
+        let mut patched = style.clone();
+        let mut conflicts = Vec::new();
+        for operation in &self.operations {
+            let id = ComponentId::for_name(&operation.name);
+            let found = patched.value(id).cloned().map(PatchValue);
+            if found != operation.before {
+                conflicts.push(Conflict {
+                    name: operation.name.clone(),
+                    expected: operation.before.clone(),
+                    found,
+                });
+            }
+            patched.restore_value(id, operation.after.clone().map(|value| value.0));
+        }
+        if conflicts.is_empty() {
+            *style = patched;
+            Ok(())
+        } else {
+            Err(conflicts)
+        }
+    }
+
+    /// Returns a patch that reverts the changes made by this patch.
+    #[must_use]
+    pub fn inverted(&self) -> Self {
+        Self {
+            operations: self
+                .operations
+                .iter()
+                .rev()
+                .map(|operation| Operation {
+                    name: operation.name.clone(),
+                    before: operation.after.clone(),
+                    after: operation.before.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns a single patch equivalent to applying `self` followed by
+    /// `next`. Operations affecting the same component are combined, and
+    /// operations that cancel each other out are removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns every [`Conflict`] found if an operation in `next` expects a
+    /// different value than the one produced by `self`.
+    pub fn composed_with(&self, next: &Self) -> Result<Self, Vec<Conflict>> {
+        let mut operations: Vec<Operation> = Vec::with_capacity(self.len() + next.len());
+        let mut conflicts = Vec::new();
+        for operation in self.operations.iter().chain(&next.operations) {
+            if let Some(existing) = operations
+                .iter_mut()
+                .find(|existing| existing.name == operation.name)
+            {
+                if existing.after != operation.before {
+                    conflicts.push(Conflict {
+                        name: operation.name.clone(),
+                        expected: operation.before.clone(),
+                        found: existing.after.clone(),
+                    });
+                }
+                existing.after.clone_from(&operation.after);
+            } else {
+                operations.push(operation.clone());
+            }
+        }
+        if conflicts.is_empty() {
+            operations.retain(|operation| operation.before != operation.after);
+            Ok(Self { operations })
+        } else {
+            Err(conflicts)
+        }
+    }
+}
+
+impl Operation {
+    /// Returns an operation that sets `component`, expecting `before` to be
+    /// the current value.
+    pub fn set<T: DynamicComponent + Clone>(before: Option<PatchValue>, component: T) -> Self {
+        Self {
+            name: component.name(),
+            before,
+            after: Some(PatchValue::component(component)),
+        }
+    }
+
+    /// Returns an operation that stores `keyword` for the component `T`,
+    /// expecting `before` to be the current value.
+    #[must_use]
+    pub fn set_keyword<T: StyleComponent>(before: Option<PatchValue>, keyword: Keyword) -> Self {
+        Self {
+            name: T::name(),
+            before,
+            after: Some(PatchValue::keyword::<T>(keyword)),
+        }
+    }
+
+    /// Returns an operation that removes the component named `name`,
+    /// expecting `before` to be the current value.
+    #[must_use]
+    pub const fn remove(name: Name, before: Option<PatchValue>) -> Self {
+        Self {
+            name,
+            before,
+            after: None,
+        }
+    }
+}
+
+impl PatchValue {
+    /// Returns a value containing `component`.
+    pub fn component<T: DynamicComponent + Clone>(component: T) -> Self {
+        Self(Value::Component(AnyComponent::new(component)))
+    }
+
+    /// Returns a value containing `keyword` for the component `T`.
+    #[must_use]
+    pub fn keyword<T: StyleComponent>(keyword: Keyword) -> Self {
+        Self(Value::Keyword {
+            keyword,
+            inherited: T::inherited(),
+        })
+    }
+
+    /// Returns the component contained in this value, if it is not a keyword.
+    #[must_use]
+    pub const fn as_component(&self) -> Option<&AnyComponent> {
+        match &self.0 {
+            Value::Component(component) => Some(component),
+            Value::Keyword { .. } => None,
+        }
+    }
+
+    /// Returns the keyword contained in this value, if it is not a component.
+    #[must_use]
+    pub const fn as_keyword(&self) -> Option<Keyword> {
+        match &self.0 {
+            Value::Keyword { keyword, .. } => Some(*keyword),
+            Value::Component(_) => None,
+        }
+    }
+}
+
+impl PartialEq for PatchValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.equivalent(&other.0)
+    }
+}
+
+impl Debug for PatchValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Value::Component(component) => component.fmt(f),
+            Value::Keyword { keyword, .. } => keyword.fmt(f),
+        }
+    }
+}
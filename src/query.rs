@@ -0,0 +1,455 @@
+use std::marker::PhantomData;
+
+use crate::world::{Column, Entity, StyleWorld};
+use crate::{ComponentId, ComponentSet, StyleComponent};
+
+/// A set of component references that can be fetched for each matching entity
+/// in a [`StyleWorld`].
+///
+/// This trait is implemented for:
+///
+/// - `&T`: requires the component, and yields a shared reference.
+/// - `&mut T`: requires the component, and yields an exclusive reference.
+/// - `Option<&T>` and `Option<&mut T>`: yields the component if present.
+/// - Tuples of up to eight queries.
+pub trait Query {
+    /// The value produced for each matching entity.
+    type Item<'a>;
+    /// Columns borrowed exclusively for the duration of a query.
+    type State;
+
+    /// Records the components accessed by this query.
+    fn access(access: &mut Access);
+
+    /// Removes the columns this query accesses exclusively from `world`.
+    fn take(world: &mut StyleWorld) -> Self::State;
+
+    /// Returns the columns removed by [`Query::take`] to `world`.
+    fn restore(world: &mut StyleWorld, state: Self::State);
+
+    /// Returns the item for `entity`, or `None` if `entity` does not match
+    /// this query.
+    fn fetch<'a>(
+        world: &'a StyleWorld,
+        state: &'a mut Self::State,
+        entity: Entity,
+    ) -> Option<Self::Item<'a>>;
+}
+
+/// A [`Query`] that only accesses components through shared references.
+pub trait ReadOnlyQuery: Query {
+    /// Returns the item for `entity`, or `None` if `entity` does not match
+    /// this query.
+    fn fetch_shared(world: &StyleWorld, entity: Entity) -> Option<Self::Item<'_>>;
+}
+
+/// The components accessed by a [`Query`].
+#[derive(Debug, Default, Clone)]
+pub struct Access {
+    required: ComponentSet,
+    excluded: ComponentSet,
+    shared: ComponentSet,
+    exclusive: ComponentSet,
+}
+
+impl Access {
+    /// Records that `id` is required to be present and is accessed through a
+    /// shared reference.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `id` is already accessed.
+    pub fn read(&mut self, id: ComponentId, required: bool) {
+        assert!(
+            !self.exclusive.contains(id),
+            "{id} is accessed mutably and immutably in the same query"
+        );
+        assert!(
+            self.shared.insert(id),
+            "{id} is accessed more than once in the same query"
+        );
+        if required {
+            self.required.insert(id);
+        }
+    }
+
+    /// Records that `id` is required to be present and is accessed through an
+    /// exclusive reference.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `id` is already accessed.
+    pub fn write(&mut self, id: ComponentId, required: bool) {
+        assert!(
+            !self.exclusive.contains(id) && !self.shared.contains(id),
+            "{id} is accessed mutably more than once in the same query"
+        );
+        self.exclusive.insert(id);
+        if required {
+            self.required.insert(id);
+        }
+    }
+
+    /// Returns the components an entity must have to match.
+    #[must_use]
+    pub const fn required(&self) -> &ComponentSet {
+        &self.required
+    }
+
+    /// Returns the components an entity must not have to match.
+    #[must_use]
+    pub const fn excluded(&self) -> &ComponentSet {
+        &self.excluded
+    }
+
+    fn matches(&self, components: &ComponentSet) -> bool {
+        components.is_superset(&self.required) && !components.intersects(&self.excluded)
+    }
+}
+
+impl<T> Query for &T
+where
+    T: StyleComponent + Clone,
+{
+    type Item<'a> = &'a T;
+    type State = ();
+
+    fn access(access: &mut Access) {
+        access.read(T::component_id(), true);
+    }
+
+    fn take(_world: &mut StyleWorld) -> Self::State {}
+
+    fn restore(_world: &mut StyleWorld, (): Self::State) {}
+
+    fn fetch<'a>(
+        world: &'a StyleWorld,
+        (): &'a mut Self::State,
+        entity: Entity,
+    ) -> Option<Self::Item<'a>> {
+        Self::fetch_shared(world, entity)
+    }
+}
+
+impl<T> ReadOnlyQuery for &T
+where
+    T: StyleComponent + Clone,
+{
+    fn fetch_shared(world: &StyleWorld, entity: Entity) -> Option<Self::Item<'_>> {
+        let index = world.slot(entity, T::component_id())?;
+        world.column::<T>()?.values().get(index)
+    }
+}
+
+impl<T> Query for Option<&T>
+where
+    T: StyleComponent + Clone,
+{
+    type Item<'a> = Option<&'a T>;
+    type State = ();
+
+    fn access(access: &mut Access) {
+        access.read(T::component_id(), false);
+    }
+
+    fn take(_world: &mut StyleWorld) -> Self::State {}
+
+    fn restore(_world: &mut StyleWorld, (): Self::State) {}
+
+    fn fetch<'a>(
+        world: &'a StyleWorld,
+        (): &'a mut Self::State,
+        entity: Entity,
+    ) -> Option<Self::Item<'a>> {
+        Self::fetch_shared(world, entity)
+    }
+}
+
+impl<T> ReadOnlyQuery for Option<&T>
+where
+    T: StyleComponent + Clone,
+{
+    fn fetch_shared(world: &StyleWorld, entity: Entity) -> Option<Self::Item<'_>> {
+        Some(<&T>::fetch_shared(world, entity))
+    }
+}
+
+/// Returns the value for `entity` from a column removed by
+/// [`StyleWorld::take_column`].
+fn fetch_mut<'a, T>(
+    world: &StyleWorld,
+    column: &'a mut Option<Column<T>>,
+    entity: Entity,
+) -> Option<&'a mut T>
+where
+    T: StyleComponent + Clone,
+{
+    let index = world.slot(entity, T::component_id())?;
+    column.as_mut()?.values_mut().get_mut(index)
+}
+
+impl<T> Query for &mut T
+where
+    T: StyleComponent + Clone,
+{
+    type Item<'a> = &'a mut T;
+    type State = Option<Column<T>>;
+
+    fn access(access: &mut Access) {
+        access.write(T::component_id(), true);
+    }
+
+    fn take(world: &mut StyleWorld) -> Self::State {
+        world.take_column()
+    }
+
+    fn restore(world: &mut StyleWorld, state: Self::State) {
+        if let Some(column) = state {
+            world.restore_column(column);
+        }
+    }
+
+    fn fetch<'a>(
+        world: &'a StyleWorld,
+        state: &'a mut Self::State,
+        entity: Entity,
+    ) -> Option<Self::Item<'a>> {
+        fetch_mut(world, state, entity)
+    }
+}
+
+impl<T> Query for Option<&mut T>
+where
+    T: StyleComponent + Clone,
+{
+    type Item<'a> = Option<&'a mut T>;
+    type State = Option<Column<T>>;
+
+    fn access(access: &mut Access) {
+        access.write(T::component_id(), false);
+    }
+
+    fn take(world: &mut StyleWorld) -> Self::State {
+        <&mut T>::take(world)
+    }
+
+    fn restore(world: &mut StyleWorld, state: Self::State) {
+        <&mut T>::restore(world, state);
+    }
+
+    fn fetch<'a>(
+        world: &'a StyleWorld,
+        state: &'a mut Self::State,
+        entity: Entity,
+    ) -> Option<Self::Item<'a>> {
+        Some(fetch_mut(world, state, entity))
+    }
+}
+
+macro_rules! impl_query_for_tuples {
+    ($($query:ident $state:ident),+) => {
+        impl<$($query: Query),+> Query for ($($query,)+) {
+            type Item<'a> = ($($query::Item<'a>,)+);
+            type State = ($($query::State,)+);
+
+            fn access(access: &mut Access) {
+                $($query::access(access);)+
+            }
+
+            fn take(world: &mut StyleWorld) -> Self::State {
+                ($($query::take(world),)+)
+            }
+
+            fn restore(world: &mut StyleWorld, ($($state,)+): Self::State) {
+                $($query::restore(world, $state);)+
+            }
+
+            fn fetch<'a>(
+                world: &'a StyleWorld,
+                ($($state,)+): &'a mut Self::State,
+                entity: Entity,
+            ) -> Option<Self::Item<'a>> {
+                Some(($($query::fetch(world, $state, entity)?,)+))
+            }
+        }
+
+        impl<$($query: ReadOnlyQuery),+> ReadOnlyQuery for ($($query,)+) {
+            fn fetch_shared(world: &StyleWorld, entity: Entity) -> Option<Self::Item<'_>> {
+                Some(($($query::fetch_shared(world, entity)?,)+))
+            }
+        }
+    };
+}
+
+impl_query_for_tuples!(A a);
+impl_query_for_tuples!(A a, B b);
+impl_query_for_tuples!(A a, B b, C c);
+impl_query_for_tuples!(A a, B b, C c, D d);
+impl_query_for_tuples!(A a, B b, C c, D d, E e);
+impl_query_for_tuples!(A a, B b, C c, D d, E e, F f);
+impl_query_for_tuples!(A a, B b, C c, D d, E e, F f, G g);
+impl_query_for_tuples!(A a, B b, C c, D d, E e, F f, G g, H h);
+
+/// An iterator over the entities in a [`StyleWorld`] matching a
+/// [`ReadOnlyQuery`].
+///
+/// This type is returned from [`StyleWorld::query`].
+pub struct QueryIter<'w, Q> {
+    world: &'w StyleWorld,
+    access: Access,
+    position: usize,
+    _query: PhantomData<fn() -> Q>,
+}
+
+impl<'w, Q> QueryIter<'w, Q>
+where
+    Q: ReadOnlyQuery,
+{
+    pub(crate) fn new(world: &'w StyleWorld) -> Self {
+        let mut access = Access::default();
+        Q::access(&mut access);
+        Self {
+            world,
+            access,
+            position: 0,
+            _query: PhantomData,
+        }
+    }
+
+    /// Only matches entities that have a value for `T`.
+    #[must_use]
+    pub fn with<T: StyleComponent>(mut self) -> Self {
+        self.access.required.insert(T::component_id());
+        self
+    }
+
+    /// Only matches entities that do not have a value for `T`.
+    #[must_use]
+    pub fn without<T: StyleComponent>(mut self) -> Self {
+        self.access.excluded.insert(T::component_id());
+        self
+    }
+}
+
+impl<'w, Q> Iterator for QueryIter<'w, Q>
+where
+    Q: ReadOnlyQuery,
+{
+    type Item = (Entity, Q::Item<'w>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(entity) = self.world.entities().get(self.position).copied() {
+            self.position += 1;
+            if self
+                .world
+                .components_of(entity)
+                .map_or(false, |components| self.access.matches(components))
+            {
+                if let Some(item) = Q::fetch_shared(self.world, entity) {
+                    return Some((entity, item));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// A query over the entities in a [`StyleWorld`] that can access components
+/// mutably.
+///
+/// This type is returned from [`StyleWorld::query_mut`].
+pub struct QueryMut<'w, Q> {
+    world: &'w mut StyleWorld,
+    access: Access,
+    _query: PhantomData<fn() -> Q>,
+}
+
+impl<'w, Q> QueryMut<'w, Q>
+where
+    Q: Query,
+{
+    pub(crate) fn new(world: &'w mut StyleWorld) -> Self {
+        let mut access = Access::default();
+        Q::access(&mut access);
+        Self {
+            world,
+            access,
+            _query: PhantomData,
+        }
+    }
+
+    /// Only matches entities that have a value for `T`.
+    #[must_use]
+    pub fn with<T: StyleComponent>(mut self) -> Self {
+        self.access.required.insert(T::component_id());
+        self
+    }
+
+    /// Only matches entities that do not have a value for `T`.
+    #[must_use]
+    pub fn without<T: StyleComponent>(mut self) -> Self {
+        self.access.excluded.insert(T::component_id());
+        self
+    }
+
+    /// Invokes `each` with every matching entity and its components.
+    pub fn for_each<F>(self, mut each: F)
+    where
+        F: for<'a> FnMut(Entity, Q::Item<'a>),
+    {
+        let mut guard = Restore::<Q> {
+            state: Some(Q::take(self.world)),
+            world: self.world,
+        };
+        let world = &*guard.world;
+        let Some(state) = guard.state.as_mut() else {
+            return;
+        };
+        for entity in world.entities() {
+            if world
+                .components_of(*entity)
+                .map_or(false, |components| self.access.matches(components))
+            {
+                if let Some(item) = Q::fetch(world, state, *entity) {
+                    each(*entity, item);
+                }
+            }
+        }
+    }
+
+    /// Returns the entities matching this query.
+    #[must_use]
+    pub fn entities(&self) -> Vec<Entity> {
+        self.world
+            .entities()
+            .iter()
+            .copied()
+            .filter(|entity| {
+                self.world
+                    .components_of(*entity)
+                    .map_or(false, |components| self.access.matches(components))
+            })
+            .collect()
+    }
+}
+
+/// Returns the columns taken by a [`Query`] to the world, even if the
+/// callback passed to [`QueryMut::for_each`] panics.
+struct Restore<'w, Q>
+where
+    Q: Query,
+{
+    world: &'w mut StyleWorld,
+    state: Option<Q::State>,
+}
+
+impl<Q> Drop for Restore<'_, Q>
+where
+    Q: Query,
+{
+    fn drop(&mut self) {
+        if let Some(state) = self.state.take() {
+            Q::restore(self.world, state);
+        }
+    }
+}
@@ -0,0 +1,357 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::{Debug, Display};
+use std::fs::Metadata;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread::JoinHandle;
+use std::time::{Duration, SystemTime};
+
+use crate::{Name, ObservableStyle, Style};
+
+/// Loads a [`Style`] from one or more files and reloads it when the files
+/// change.
+///
+/// Each file is parsed into a [`Style`] by the parser provided to
+/// [`StyleWatcher::new`]. When multiple files are watched, later files take
+/// precedence over earlier files, as if each file's style was
+/// [merged](Style::merged_with) on top of the previous files.
+///
+/// Reloaded styles are published to the [`ObservableStyle`] returned by
+/// [`StyleWatcher::observable`] and to callbacks registered with
+/// [`StyleWatcher::on_reload`]. If a file cannot be read or parsed, the last
+/// successfully loaded style is kept.
+///
+/// This type is only available when the `hot-reload` feature is enabled.
+#[derive(Clone)]
+pub struct StyleWatcher(Arc<Watcher>);
+
+struct Watcher {
+    files: Vec<PathBuf>,
+    parser: Box<Parser>,
+    style: ObservableStyle,
+    state: Mutex<WatchState>,
+}
+
+type Parser = dyn Fn(&str) -> Result<Style, String> + Send + Sync;
+type Callback = dyn Fn(&ReloadEvent) + Send + Sync;
+
+struct WatchState {
+    stamps: Vec<Option<Stamp>>,
+    callbacks: Vec<Arc<Callback>>,
+}
+
+/// The coarsest modification time resolution of commonly used file systems.
+/// FAT records modification times in 2 second increments.
+const TIMESTAMP_GRANULARITY: Duration = Duration::from_secs(2);
+
+/// The modification time, length and contents hash of a file, used to detect
+/// changes.
+#[derive(Clone, Copy, Eq, PartialEq)]
+struct Stamp {
+    modified: Option<SystemTime>,
+    len: u64,
+    hash: Option<u64>,
+    /// True once the file's modification time is old enough that any further
+    /// write must change it.
+    settled: bool,
+}
+
+/// The result of a reload observed by a [`StyleWatcher`].
+#[derive(Debug, Clone)]
+pub enum ReloadEvent {
+    /// The style was reloaded successfully.
+    Reloaded(Reload),
+    /// The style failed to reload. The previous style is still in use.
+    Failed(Arc<ReloadError>),
+}
+
+/// A successfully reloaded [`Style`].
+#[derive(Debug, Clone)]
+pub struct Reload {
+    /// The newly loaded style.
+    pub style: Style,
+    /// The names of every component that was added, removed, or changed.
+    pub changed: Vec<Name>,
+}
+
+/// An error loading a file watched by a [`StyleWatcher`].
+#[derive(Debug)]
+pub enum ReloadError {
+    /// The file could not be read.
+    Io {
+        /// The path of the file.
+        path: PathBuf,
+        /// The error that occurred.
+        error: std::io::Error,
+    },
+    /// The file's contents could not be parsed.
+    Parse {
+        /// The path of the file.
+        path: PathBuf,
+        /// The error returned by the parser.
+        message: String,
+    },
+}
+
+impl Display for ReloadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReloadError::Io { path, error } => {
+                write!(f, "error reading {}: {error}", path.display())
+            }
+            ReloadError::Parse { path, message } => {
+                write!(f, "error parsing {}: {message}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReloadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReloadError::Io { error, .. } => Some(error),
+            ReloadError::Parse { .. } => None,
+        }
+    }
+}
+
+impl Debug for StyleWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StyleWatcher")
+            .field("files", &self.0.files)
+            .field("style", &self.0.style)
+            .finish_non_exhaustive()
+    }
+}
+
+impl StyleWatcher {
+    /// Loads `files` using `parser`, returning a watcher that can reload the
+    /// files when they change.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any file cannot be read or parsed.
+    pub fn new<E: Display>(
+        files: impl IntoIterator<Item = impl Into<PathBuf>>,
+        parser: impl Fn(&str) -> Result<Style, E> + Send + Sync + 'static,
+    ) -> Result<Self, ReloadError> {
+        let files = files.into_iter().map(Into::into).collect::<Vec<_>>();
+        let watcher = Watcher {
+            state: Mutex::new(WatchState {
+                stamps: vec![None; files.len()],
+                callbacks: Vec::new(),
+            }),
+            files,
+            parser: Box::new(move |source| parser(source).map_err(|err| err.to_string())),
+            style: ObservableStyle::default(),
+        };
+        let style = {
+            let mut state = watcher.state();
+            watcher.load(&mut state)?
+        };
+        watcher.style.replace(style);
+        Ok(Self(Arc::new(watcher)))
+    }
+
+    /// Returns the watched files.
+    #[must_use]
+    pub fn files(&self) -> &[PathBuf] {
+        &self.0.files
+    }
+
+    /// Returns a copy of the most recently loaded style.
+    #[must_use]
+    pub fn style(&self) -> Style {
+        self.0.style.snapshot()
+    }
+
+    /// Returns the observable style that reloaded styles are published to.
+    #[must_use]
+    pub fn observable(&self) -> &ObservableStyle {
+        &self.0.style
+    }
+
+    /// Invokes `callback` each time a reload succeeds or fails.
+    pub fn on_reload(&self, callback: impl Fn(&ReloadEvent) + Send + Sync + 'static) {
+        self.0.state().callbacks.push(Arc::new(callback));
+    }
+
+    /// Reloads the style if any watched file has changed since it was last
+    /// loaded. Returns the reloaded style if a change was found.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be read or parsed. The previously
+    /// loaded style remains in use.
+    pub fn check(&self) -> Result<Option<Reload>, Arc<ReloadError>> {
+        let mut state = self.0.state();
+        let changed = self
+            .0
+            .files
+            .iter()
+            .zip(&mut state.stamps)
+            .any(|(path, stamp)| is_stale(path, stamp.as_mut()));
+        if !changed {
+            return Ok(None);
+        }
+
+        let event = match self.0.load(&mut state) {
+            Ok(style) => {
+                let (_, patch) = self.0.style.replace_with_diff(style.clone());
+                let changed = patch
+                    .operations()
+                    .iter()
+                    .map(|operation| operation.name.clone())
+                    .collect();
+                ReloadEvent::Reloaded(Reload { style, changed })
+            }
+            Err(err) => ReloadEvent::Failed(Arc::new(err)),
+        };
+        let callbacks = state.callbacks.clone();
+        drop(state);
+        for callback in callbacks {
+            callback(&event);
+        }
+        match event {
+            ReloadEvent::Reloaded(reload) => Ok(Some(reload)),
+            ReloadEvent::Failed(err) => Err(err),
+        }
+    }
+
+    /// Spawns a thread that calls [`StyleWatcher::check`] every `interval`
+    /// until the returned handle is dropped.
+    #[must_use]
+    pub fn watch(&self, interval: Duration) -> WatchHandle {
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread = std::thread::spawn({
+            let watcher = self.clone();
+            let stop = stop.clone();
+            move || {
+                while !stop.load(Ordering::Relaxed) {
+                    // Errors are reported to the reload callbacks.
+                    let _result = watcher.check();
+                    std::thread::park_timeout(interval);
+                }
+            }
+        });
+        WatchHandle {
+            stop,
+            thread: Some(thread),
+        }
+    }
+}
+
+impl Watcher {
+    fn state(&self) -> MutexGuard<'_, WatchState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Reads and parses every file. The recorded stamps are updated even when
+    /// loading fails so that a broken file is not reparsed until it changes
+    /// again.
+    fn load(&self, state: &mut WatchState) -> Result<Style, ReloadError> {
+        let mut style = Style::new();
+        let mut result = Ok(());
+        for (path, stamp) in self.files.iter().zip(&mut state.stamps) {
+            let contents = std::fs::read(path);
+            *stamp = std::fs::metadata(path)
+                .ok()
+                .map(|metadata| Stamp::new(&metadata, contents.as_deref().ok()));
+            if result.is_err() {
+                continue;
+            }
+            result = contents
+                .and_then(|contents| {
+                    String::from_utf8(contents)
+                        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+                })
+                .map_err(|error| ReloadError::Io {
+                    path: path.clone(),
+                    error,
+                })
+                .and_then(|source| {
+                    (self.parser)(&source).map_err(|message| ReloadError::Parse {
+                        path: path.clone(),
+                        message,
+                    })
+                })
+                .map(|loaded| style = loaded.merged_with(&style));
+        }
+        result.map(|()| style)
+    }
+}
+
+impl Stamp {
+    fn new(metadata: &Metadata, contents: Option<&[u8]>) -> Self {
+        Self {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+            hash: contents.map(hash_of),
+            settled: metadata.modified().map_or(false, is_settled),
+        }
+    }
+
+    fn matches(&self, metadata: &Metadata) -> bool {
+        self.modified == metadata.modified().ok() && self.len == metadata.len()
+    }
+}
+
+/// Returns true if more than [`TIMESTAMP_GRANULARITY`] has elapsed since
+/// `modified`.
+fn is_settled(modified: SystemTime) -> bool {
+    SystemTime::now()
+        .duration_since(modified)
+        .map_or(false, |elapsed| elapsed > TIMESTAMP_GRANULARITY)
+}
+
+fn hash_of(contents: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    contents.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns true if the file at `path` no longer matches `stamp`.
+///
+/// The file's contents are only hashed while its modification time is too
+/// recent to be trusted. Once the contents have been confirmed unchanged after
+/// the modification time settles, only the metadata is compared.
+fn is_stale(path: &Path, stamp: Option<&mut Stamp>) -> bool {
+    match (std::fs::metadata(path), stamp) {
+        (Err(_), None) => false,
+        (Ok(metadata), Some(stamp)) if stamp.matches(&metadata) => {
+            if stamp.settled {
+                return false;
+            }
+            // Edits made within the file system's timestamp granularity can
+            // keep both the modification time and the length unchanged.
+            let settled = stamp.modified.map_or(false, is_settled);
+            if std::fs::read(path).ok().as_deref().map(hash_of) == stamp.hash {
+                stamp.settled = settled;
+                false
+            } else {
+                true
+            }
+        }
+        _ => true,
+    }
+}
+
+/// A handle to a thread spawned by [`StyleWatcher::watch`]. The thread is
+/// stopped when this handle is dropped.
+#[derive(Debug)]
+pub struct WatchHandle {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for WatchHandle {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            thread.thread().unpark();
+            let _result = thread.join();
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use crate::any::AnyComponent;
+use crate::style::ByAuthority;
+use crate::{Identifier, Keyword, Name, Style, StyleComponent};
+
+/// A borrowed view of a [`Style`] that only contains components whose names
+/// have a single [authority](Name::authority).
+///
+/// This allows a library to process its own components without observing
+/// components defined by other authorities. Created by [`Style::scoped`].
+#[derive(Debug, Clone)]
+pub struct ScopedStyle<'a> {
+    style: &'a Style,
+    authority: Identifier,
+}
+
+impl<'a> ScopedStyle<'a> {
+    pub(crate) const fn new(style: &'a Style, authority: Identifier) -> Self {
+        Self { style, authority }
+    }
+
+    /// Returns the authority this view is scoped to.
+    #[must_use]
+    pub const fn authority(&self) -> &Identifier {
+        &self.authority
+    }
+
+    /// Returns the style component of type `T`, if present and `T`'s name has
+    /// this view's authority.
+    #[must_use]
+    pub fn get<T: StyleComponent>(&self) -> Option<&'a T> {
+        if T::name().authority == self.authority {
+            self.style.get::<T>()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the style component named `name`, if present and `name` has
+    /// this view's authority.
+    #[must_use]
+    pub fn get_by_name(&self, name: &Name) -> Option<&'a AnyComponent> {
+        if name.authority == self.authority {
+            self.style.get_by_name(name)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the keyword stored for the component named `name`, if present
+    /// and `name` has this view's authority.
+    #[must_use]
+    pub fn keyword(&self, name: &Name) -> Option<Keyword> {
+        if name.authority == self.authority {
+            self.style.keyword(name)
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if this view contains a component of type `T`.
+    #[must_use]
+    pub fn contains<T: StyleComponent>(&self) -> bool {
+        self.get::<T>().is_some()
+    }
+
+    /// Returns an iterator over the components in this view.
+    #[must_use]
+    pub fn iter(&self) -> ByAuthority<'_> {
+        self.into_iter()
+    }
+
+    /// Returns the number of components in this view.
+    ///
+    /// This is not a constant-time operation: every component in the
+    /// underlying style is visited to count the ones with this view's
+    /// authority.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Returns true if this view contains no components.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    /// Returns a new [`Style`] containing a copy of each component and
+    /// [`Keyword`] in this view.
+    ///
+    /// The result is the same style that [`Style::split_by_authority`]
+    /// returns for this view's authority.
+    #[must_use]
+    pub fn to_style(&self) -> Style {
+        self.style.with_authority(&self.authority)
+    }
+}
+
+impl<'s> IntoIterator for &'s ScopedStyle<'_> {
+    type IntoIter = ByAuthority<'s>;
+    type Item = &'s AnyComponent;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.style.authority(&self.authority)
+    }
+}
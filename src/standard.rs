@@ -0,0 +1,391 @@
+//! A library of commonly used style components.
+//!
+//! All components in this module use the reserved `stylecs` authority.
+
+#[cfg(test)]
+use crate::Style;
+use crate::{
+    static_name, Axis, Dimension, FontSizeSource, Name, ResolutionContext, StaticName,
+    StyleComponent,
+};
+
+/// The authority used by all components in this module.
+pub use crate::AUTHORITY;
+
+macro_rules! standard_component {
+    ($(#[$meta:meta])* $type:ident($inner:ty), $name:literal) => {
+        $(#[$meta])*
+        #[derive(Default, Debug, Clone, Copy, PartialEq)]
+        pub struct $type(pub $inner);
+
+        impl StyleComponent for $type {
+            fn name() -> Name {
+                static NAME: StaticName = static_name!(AUTHORITY, $name);
+                NAME.to_name()
+            }
+
+            fn merge(&mut self, other: &Self) {
+                self.0 = self.0.or(other.0);
+            }
+
+            fn resolve_units(&mut self, context: &ResolutionContext) {
+                self.0 = self.0.to_absolute(context);
+            }
+
+            fn equivalent(&self, other: &Self) -> bool {
+                self == other
+            }
+        }
+
+        impl From<$inner> for $type {
+            fn from(value: $inner) -> Self {
+                Self(value)
+            }
+        }
+    };
+}
+
+standard_component!(
+    /// The space between a widget's border and its contents.
+    Padding(Edges<Dimension>),
+    "padding"
+);
+standard_component!(
+    /// The space around a widget's border.
+    Margin(Edges<Dimension>),
+    "margin"
+);
+standard_component!(
+    /// The width of each side of a widget's border.
+    BorderWidth(Edges<Dimension>),
+    "border_width"
+);
+standard_component!(
+    /// The radius of each corner of a widget's border.
+    CornerRadii(Corners<Dimension>),
+    "corner_radii"
+);
+standard_component!(
+    /// The minimum size of a widget.
+    MinSize(Size<Dimension>),
+    "min_size"
+);
+standard_component!(
+    /// The maximum size of a widget.
+    MaxSize(Size<Dimension>),
+    "max_size"
+);
+
+/// The size of text, inherited by default.
+///
+/// [`Unit::Em`](crate::Unit::Em) and [`Unit::Percent`](crate::Unit::Percent)
+/// are resolved relative to the parent's font size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FontSize(pub Dimension);
+
+impl StyleComponent for FontSize {
+    fn name() -> Name {
+        static NAME: StaticName = static_name!(AUTHORITY, "font_size");
+        NAME.to_name()
+    }
+
+    fn inherited() -> bool {
+        true
+    }
+
+    fn resolve_units(&mut self, context: &ResolutionContext) {
+        if self.0.unit == crate::Unit::Percent {
+            self.0 = Dimension::px(self.0.value / 100. * context.font_size);
+        } else {
+            self.0 = self.0.to_absolute(context, Axis::Vertical);
+        }
+    }
+
+    fn equivalent(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+impl FontSize {
+    /// Returns a [`FontSizeSource`] that uses this component as the basis for
+    /// resolving [`Unit::Em`](crate::Unit::Em).
+    #[must_use]
+    pub fn source() -> FontSizeSource {
+        FontSizeSource::new(|size: &Self| size.0)
+    }
+}
+
+/// A value for each side of a rectangle. Each side is optional, allowing
+/// partially specified values to be merged together.
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Edges<T> {
+    /// The left side.
+    pub left: Option<T>,
+    /// The top side.
+    pub top: Option<T>,
+    /// The right side.
+    pub right: Option<T>,
+    /// The bottom side.
+    pub bottom: Option<T>,
+}
+
+impl<T> Edges<T> {
+    /// Returns edges with `value` for all sides.
+    #[must_use]
+    pub fn uniform(value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            left: Some(value.clone()),
+            top: Some(value.clone()),
+            right: Some(value.clone()),
+            bottom: Some(value),
+        }
+    }
+
+    /// Returns edges with `value` for the left and right sides.
+    #[must_use]
+    pub fn horizontal(value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            left: Some(value.clone()),
+            right: Some(value),
+            top: None,
+            bottom: None,
+        }
+    }
+
+    /// Returns edges with `value` for the top and bottom sides.
+    #[must_use]
+    pub fn vertical(value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            top: Some(value.clone()),
+            bottom: Some(value),
+            left: None,
+            right: None,
+        }
+    }
+
+    /// Returns a new value using each side of `self` that is present, falling
+    /// back to the corresponding side of `other`.
+    #[must_use]
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            left: self.left.or(other.left),
+            top: self.top.or(other.top),
+            right: self.right.or(other.right),
+            bottom: self.bottom.or(other.bottom),
+        }
+    }
+
+    /// Returns the result of invoking `map` on each side that is present.
+    #[must_use]
+    pub fn map<R>(self, mut map: impl FnMut(T) -> R) -> Edges<R> {
+        Edges {
+            left: self.left.map(&mut map),
+            top: self.top.map(&mut map),
+            right: self.right.map(&mut map),
+            bottom: self.bottom.map(&mut map),
+        }
+    }
+}
+
+impl Edges<Dimension> {
+    /// Returns these edges with each dimension converted to an absolute
+    /// dimension. The left and right sides are resolved horizontally, and the
+    /// top and bottom sides are resolved vertically.
+    #[must_use]
+    pub fn to_absolute(self, context: &ResolutionContext) -> Self {
+        Self {
+            left: self
+                .left
+                .map(|left| left.to_absolute(context, Axis::Horizontal)),
+            top: self.top.map(|top| top.to_absolute(context, Axis::Vertical)),
+            right: self
+                .right
+                .map(|right| right.to_absolute(context, Axis::Horizontal)),
+            bottom: self
+                .bottom
+                .map(|bottom| bottom.to_absolute(context, Axis::Vertical)),
+        }
+    }
+}
+
+/// A value for each corner of a rectangle. Each corner is optional, allowing
+/// partially specified values to be merged together.
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Corners<T> {
+    /// The top-left corner.
+    pub top_left: Option<T>,
+    /// The top-right corner.
+    pub top_right: Option<T>,
+    /// The bottom-right corner.
+    pub bottom_right: Option<T>,
+    /// The bottom-left corner.
+    pub bottom_left: Option<T>,
+}
+
+impl<T> Corners<T> {
+    /// Returns corners with `value` for all corners.
+    #[must_use]
+    pub fn uniform(value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            top_left: Some(value.clone()),
+            top_right: Some(value.clone()),
+            bottom_right: Some(value.clone()),
+            bottom_left: Some(value),
+        }
+    }
+
+    /// Returns a new value using each corner of `self` that is present,
+    /// falling back to the corresponding corner of `other`.
+    #[must_use]
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            top_left: self.top_left.or(other.top_left),
+            top_right: self.top_right.or(other.top_right),
+            bottom_right: self.bottom_right.or(other.bottom_right),
+            bottom_left: self.bottom_left.or(other.bottom_left),
+        }
+    }
+
+    /// Returns the result of invoking `map` on each corner that is present.
+    #[must_use]
+    pub fn map<R>(self, mut map: impl FnMut(T) -> R) -> Corners<R> {
+        Corners {
+            top_left: self.top_left.map(&mut map),
+            top_right: self.top_right.map(&mut map),
+            bottom_right: self.bottom_right.map(&mut map),
+            bottom_left: self.bottom_left.map(&mut map),
+        }
+    }
+}
+
+impl Corners<Dimension> {
+    /// Returns these corners with each dimension converted to an absolute
+    /// dimension. Each corner is resolved horizontally.
+    #[must_use]
+    pub fn to_absolute(self, context: &ResolutionContext) -> Self {
+        self.map(|corner| corner.to_absolute(context, Axis::Horizontal))
+    }
+}
+
+/// A two-dimensional size. Each dimension is optional, allowing partially
+/// specified values to be merged together.
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Size<T> {
+    /// The horizontal measurement.
+    pub width: Option<T>,
+    /// The vertical measurement.
+    pub height: Option<T>,
+}
+
+impl<T> Size<T> {
+    /// Returns a size with both dimensions specified.
+    #[must_use]
+    pub const fn new(width: T, height: T) -> Self {
+        Self {
+            width: Some(width),
+            height: Some(height),
+        }
+    }
+
+    /// Returns a new value using each dimension of `self` that is present,
+    /// falling back to the corresponding dimension of `other`.
+    #[must_use]
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            width: self.width.or(other.width),
+            height: self.height.or(other.height),
+        }
+    }
+
+    /// Returns the result of invoking `map` on each dimension that is present.
+    #[must_use]
+    pub fn map<R>(self, mut map: impl FnMut(T) -> R) -> Size<R> {
+        Size {
+            width: self.width.map(&mut map),
+            height: self.height.map(&mut map),
+        }
+    }
+}
+
+impl Size<Dimension> {
+    /// Returns this size with each dimension converted to an absolute
+    /// dimension.
+    #[must_use]
+    pub fn to_absolute(self, context: &ResolutionContext) -> Self {
+        Self {
+            width: self
+                .width
+                .map(|width| width.to_absolute(context, Axis::Horizontal)),
+            height: self
+                .height
+                .map(|height| height.to_absolute(context, Axis::Vertical)),
+        }
+    }
+}
+
+#[test]
+fn edges_merge() {
+    let merged = Style::new()
+        .with(Padding(Edges::horizontal(Dimension::px(10.))))
+        .merged_with(&Style::new().with(Padding(Edges::uniform(Dimension::em(1.)))));
+    assert_eq!(
+        merged.get::<Padding>(),
+        Some(&Padding(Edges {
+            left: Some(Dimension::px(10.)),
+            right: Some(Dimension::px(10.)),
+            top: Some(Dimension::em(1.)),
+            bottom: Some(Dimension::em(1.)),
+        }))
+    );
+    assert_eq!(Padding::name(), Name::new(AUTHORITY, "padding").unwrap());
+    assert!(!Padding::inherited());
+}
+
+#[test]
+fn em_resolution() {
+    let context = ResolutionContext {
+        font_size: 10.,
+        container_width: 100.,
+        ..ResolutionContext::default()
+    };
+    let root = Style::new()
+        .with(FontSize(Dimension::pt(15.)))
+        .resolved_inherited_from(&Style::new(), &context, &FontSize::source());
+    assert_eq!(root.get::<FontSize>(), Some(&FontSize(Dimension::px(20.))));
+
+    let child = Style::new()
+        .with(FontSize(Dimension::em(2.)))
+        .with(Padding(Edges {
+            left: Some(Dimension::percent(10.)),
+            top: Some(Dimension::em(0.5)),
+            ..Edges::default()
+        }))
+        .resolved_inherited_from(&root, &context, &FontSize::source());
+    assert_eq!(child.get::<FontSize>(), Some(&FontSize(Dimension::px(40.))));
+    assert_eq!(
+        child.get::<Padding>(),
+        Some(&Padding(Edges {
+            left: Some(Dimension::px(10.)),
+            top: Some(Dimension::px(20.)),
+            ..Edges::default()
+        }))
+    );
+
+    let grandchild = Style::new().resolved_inherited_from(&child, &context, &FontSize::source());
+    assert_eq!(
+        grandchild.get::<FontSize>(),
+        Some(&FontSize(Dimension::px(40.)))
+    );
+}
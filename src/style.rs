@@ -1,21 +1,119 @@
 use kempt::Map;
 
+use crate::aliases::report_deprecated_alias;
 use crate::any::AnyComponent;
 use crate::components::DynamicComponent;
-use crate::names::NameKey;
-use crate::{Name, StyleComponent};
+use crate::patch::{MergeConflict, PatchValue, ThreeWayMerge};
+use crate::{
+    Axis, ComponentId, ComponentSet, ConversionRegistry, DynamicValue, FontSizeSource, Identifier,
+    InitialValues, Name, ResolutionContext, ScopedStyle, StyleComponent, Violation,
+};
 
 /// A set of style components.
+///
+/// Components are stored sorted by their [`ComponentId`], making lookups by
+/// type or id efficient. Lookups by [`Name`] must first find the name's id in a
+/// global registry, making them slower.
 #[derive(Default, Clone)]
 pub struct Style {
-    components: Map<NameKey<'static>, AnyComponent>,
+    components: Map<ComponentId, Entry>,
+    /// The ids of all entries that contain a component. Entries containing a
+    /// keyword are not included.
+    present: ComponentSet,
+    generation: Generation,
+}
+
+#[derive(Clone)]
+struct Entry {
+    value: Value,
+    generation: Generation,
+}
+
+impl Entry {
+    fn component(&self) -> Option<&AnyComponent> {
+        match &self.value {
+            Value::Component(component) => Some(component),
+            Value::Keyword { .. } => None,
+        }
+    }
+
+    fn component_mut(&mut self) -> Option<&mut AnyComponent> {
+        match &mut self.value {
+            Value::Component(component) => Some(component),
+            Value::Keyword { .. } => None,
+        }
+    }
+
+    /// Invokes `modify` with this entry's component, if present. The entry is
+    /// only marked as modified during `generation` if the component is no
+    /// longer [equivalent](AnyComponent::equivalent) to its previous value.
+    fn modify_component(&mut self, generation: Generation, modify: impl FnOnce(&mut AnyComponent)) {
+        let Value::Component(component) = &mut self.value else {
+            return;
+        };
+        let previous = component.clone();
+        modify(component);
+        if !component.equivalent(&previous) {
+            self.generation = generation;
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) enum Value {
+    Component(AnyComponent),
+    Keyword { keyword: Keyword, inherited: bool },
+}
+
+impl Value {
+    fn inherited(&self) -> bool {
+        match self {
+            Value::Component(component) => component.inherited(),
+            Value::Keyword { inherited, .. } => *inherited,
+        }
+    }
+
+    /// Returns true if both values are equivalent components or the same
+    /// keyword.
+    pub(crate) fn equivalent(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::Component(a), Value::Component(b)) => a.equivalent(b),
+            (Value::Keyword { keyword: a, .. }, Value::Keyword { keyword: b, .. }) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// Returns true if `a` and `b` are both absent or are
+/// [equivalent](Value::equivalent).
+pub(crate) fn equivalent(a: Option<&Value>, b: Option<&Value>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a.equivalent(b),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// Merges `theirs` into `ours`, returning `None` if either is not a component
+/// or if the merge did not change `ours`.
+fn combined(ours: Option<&Value>, theirs: Option<&Value>) -> Option<AnyComponent> {
+    let (Some(Value::Component(ours)), Some(Value::Component(theirs))) = (ours, theirs) else {
+        return None;
+    };
+    let merged = ours.clone().merged_with(theirs);
+    (!merged.equivalent(ours)).then_some(merged)
 }
 
 impl std::fmt::Debug for Style {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut t = f.debug_tuple("Style");
-        for component in self.components.values() {
-            t.field(component);
+        for field in &self.components {
+            match &field.value.value {
+                Value::Component(component) => t.field(component),
+                Value::Keyword { keyword, .. } => {
+                    t.field(&format_args!("{}: {keyword:?}", field.key().name()))
+                }
+            };
         }
         t.finish()
     }
@@ -34,14 +132,109 @@ impl Style {
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
             components: Map::with_capacity(capacity),
+            present: ComponentSet::new(),
+            generation: Generation::default(),
         }
     }
 
     /// Adds a component to this style. Any existing values of the same type
     /// will be replaced.
     pub fn push<T: DynamicComponent + Clone>(&mut self, component: T) {
-        let c = AnyComponent::new(component);
-        self.components.insert(NameKey::from(c.name()), c);
+        self.push_any(AnyComponent::new(component));
+    }
+
+    /// Adds a boxed component to this style. Any existing values of the same
+    /// type will be replaced.
+    ///
+    /// A [`DynamicValue`] named with a deprecated [alias](Name::register_alias)
+    /// is renamed to the alias's canonical name.
+    pub fn push_any(&mut self, mut component: AnyComponent) {
+        component.canonicalize_name();
+        let id = component.component_id();
+        self.generation = self.generation.next();
+        self.present.insert(id);
+        self.components.insert(
+            id,
+            Entry {
+                value: Value::Component(component),
+                generation: self.generation,
+            },
+        );
+    }
+
+    /// Normalizes and validates `component` before adding it to this style.
+    /// Any existing values of the same type will be replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`Violation`] if `component` is invalid after being
+    /// normalized. The style is not modified when an error is returned.
+    pub fn push_checked<T: DynamicComponent + Clone>(
+        &mut self,
+        mut component: T,
+    ) -> Result<(), Violation> {
+        component.normalize();
+        component.validate().map_err(|error| Violation {
+            name: component.name(),
+            error,
+        })?;
+        self.push(component);
+        Ok(())
+    }
+
+    /// Normalizes and validates `component` before adding it to the style and
+    /// returning it. Any existing values of the same type will be replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`Violation`] if `component` is invalid after being
+    /// normalized.
+    pub fn with_checked<T: DynamicComponent + Clone>(
+        mut self,
+        component: T,
+    ) -> Result<Self, Violation> {
+        self.push_checked(component)?;
+        Ok(self)
+    }
+
+    /// Stores `keyword` in place of the value for the component of type `T`.
+    /// Any existing value of the same type will be replaced.
+    ///
+    /// Keywords are not returned by [`Style::get`] or when iterating. Instead,
+    /// they control how this style is combined with others in
+    /// [`Style::merged_with`] and [`Style::inherited_from`].
+    pub fn push_keyword<T: StyleComponent>(&mut self, keyword: Keyword) {
+        let id = T::component_id();
+        self.generation = self.generation.next();
+        self.present.remove(id);
+        self.components.insert(
+            id,
+            Entry {
+                value: Value::Keyword {
+                    keyword,
+                    inherited: T::inherited(),
+                },
+                generation: self.generation,
+            },
+        );
+    }
+
+    /// Stores `keyword` in place of the value for the component of type `T`
+    /// and returns the style. Any existing value of the same type will be
+    /// replaced.
+    #[must_use]
+    pub fn with_keyword<T: StyleComponent>(mut self, keyword: Keyword) -> Self {
+        self.push_keyword::<T>(keyword);
+        self
+    }
+
+    /// Returns the keyword stored for the component named `name`, if present.
+    #[must_use]
+    pub fn keyword(&self, name: &Name) -> Option<Keyword> {
+        match self.components.get(&ComponentId::find(name)?)?.value {
+            Value::Keyword { keyword, .. } => Some(keyword),
+            Value::Component(_) => None,
+        }
     }
 
     /// Adds a component to the style and returns it. Any existing values of the
@@ -55,70 +248,632 @@ impl Style {
     /// Returns the style component of type `T`, if present.
     #[must_use]
     pub fn get<T: StyleComponent>(&self) -> Option<&T> {
-        self.components
-            .get(&NameKey::from(T::name()))
-            .and_then(AnyComponent::get)
+        self.get_by_id(T::component_id())?.get()
+    }
+
+    /// Returns the style component with the given id, if present.
+    #[must_use]
+    pub fn get_by_id(&self, id: ComponentId) -> Option<&AnyComponent> {
+        self.components.get(&id).and_then(Entry::component)
+    }
+
+    /// Returns a mutable reference to the style component of type `T`, if
+    /// present. The component is considered modified when found, advancing
+    /// this style's [generation](Self::generation).
+    #[must_use]
+    pub fn get_mut<T: StyleComponent>(&mut self) -> Option<&mut T> {
+        let entry = self.components.get_mut(&T::component_id())?;
+        entry.component_mut()?.get_mut::<T>()?;
+        self.generation = self.generation.next();
+        entry.generation = self.generation;
+        entry.component_mut()?.get_mut()
+    }
+
+    /// Removes and returns the style component of type `T`, if present. A
+    /// [`Keyword`] stored for `T` is also removed.
+    pub fn remove<T: StyleComponent>(&mut self) -> Option<T> {
+        let id = T::component_id();
+        if let Value::Component(component) = &self.components.get(&id)?.value {
+            component.get::<T>()?;
+        }
+        self.remove_by_id(id)
+            .and_then(|component| component.into_inner().ok())
+    }
+
+    /// Removes and returns the style component with the given name, if
+    /// present. A [`Keyword`] stored for this name is also removed.
+    pub fn remove_by_name(&mut self, name: &Name) -> Option<AnyComponent> {
+        self.remove_by_id(ComponentId::find(name)?)
+    }
+
+    /// Removes and returns the style component with the given id, if present.
+    /// A [`Keyword`] stored for this id is also removed.
+    pub fn remove_by_id(&mut self, id: ComponentId) -> Option<AnyComponent> {
+        let removed = self.components.remove(&id)?;
+        self.present.remove(id);
+        self.generation = self.generation.next();
+        match removed.value.value {
+            Value::Component(component) => Some(component),
+            Value::Keyword { .. } => None,
+        }
+    }
+
+    /// Returns the value stored for `id`, which may be a [`Keyword`].
+    pub(crate) fn value(&self, id: ComponentId) -> Option<&Value> {
+        self.components.get(&id).map(|entry| &entry.value)
+    }
+
+    /// Replaces the value stored for `id`, removing the entry when `value` is
+    /// `None`.
+    pub(crate) fn restore_value(&mut self, id: ComponentId, value: Option<Value>) {
+        self.generation = self.generation.next();
+        if let Some(value) = value {
+            if matches!(value, Value::Component(_)) {
+                self.present.insert(id);
+            } else {
+                self.present.remove(id);
+            }
+            self.components.insert(
+                id,
+                Entry {
+                    value,
+                    generation: self.generation,
+                },
+            );
+        } else {
+            self.components.remove(&id);
+            self.present.remove(id);
+        }
+    }
+
+    /// Returns the ids of every component and keyword stored in this style.
+    pub(crate) fn ids(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.components.keys().copied()
     }
 
     /// Returns the style component of type `T`, if present.
     #[must_use]
     pub fn get_by_name(&self, name: &Name) -> Option<&AnyComponent> {
-        self.components.get(&NameKey::from(name))
+        self.get_by_id(ComponentId::find(name)?)
+    }
+
+    /// Returns true if this style contains the component of type `T`.
+    #[must_use]
+    pub fn contains<T: StyleComponent>(&self) -> bool {
+        self.present.contains(T::component_id())
+    }
+
+    /// Returns true if this style contains the component with the given id.
+    #[must_use]
+    pub fn contains_id(&self, id: ComponentId) -> bool {
+        self.present.contains(id)
+    }
+
+    /// Returns true if this style contains the component named `name`.
+    #[must_use]
+    pub fn contains_name(&self, name: &Name) -> bool {
+        ComponentId::find(name).map_or(false, |id| self.contains_id(id))
+    }
+
+    /// Returns true if this style contains at least one of the components in
+    /// `components`.
+    #[must_use]
+    pub fn contains_any(&self, components: &ComponentSet) -> bool {
+        self.present.intersects(components)
+    }
+
+    /// Returns true if this style contains every component in `components`.
+    #[must_use]
+    pub fn contains_all(&self, components: &ComponentSet) -> bool {
+        self.present.is_superset(components)
+    }
+
+    /// Returns the set of components contained in this style. Keywords are
+    /// not included.
+    #[must_use]
+    pub const fn component_set(&self) -> &ComponentSet {
+        &self.present
     }
 
     /// Returns the style component of type `T`. If not present, `T::default()`
     /// will be returned.
+    ///
+    /// To use each component's [initial
+    /// value](StyleComponent::initial_value) instead, see [`Style::computed`].
     #[must_use]
     pub fn get_or_default<T: StyleComponent + Default + Clone>(&self) -> T {
         self.get::<T>().cloned().unwrap_or_default()
     }
 
+    /// Returns the current generation of this style.
+    ///
+    /// The generation is advanced each time this style is modified. Comparing
+    /// generations is only meaningful for the same style or clones of it.
+    #[must_use]
+    pub const fn generation(&self) -> Generation {
+        self.generation
+    }
+
+    /// Returns the generation of the style when the component or keyword
+    /// named `name` was last modified, or `None` if neither is present.
+    #[must_use]
+    pub fn component_generation(&self, name: &Name) -> Option<Generation> {
+        self.components
+            .get(&ComponentId::find(name)?)
+            .map(|entry| entry.generation)
+    }
+
+    /// Returns an iterator over the components that have been modified after
+    /// `generation`.
+    ///
+    /// Removed components are not returned. A change in
+    /// [`Style::generation()`] without any returned components indicates that
+    /// one or more components were removed.
+    #[must_use]
+    pub fn changed_since(&self, generation: Generation) -> ChangedSince<'_> {
+        ChangedSince {
+            entries: self.components.values(),
+            generation,
+        }
+    }
+
     /// Returns a new [`Style`], merging the components of `self` with `other`.
     /// If both `self` and `other` contain a value of the same type, the value
     /// in `self` will be used.
+    ///
+    /// Keywords in `self` take precedence over values in `other`, except for
+    /// [`Keyword::Revert`], which is replaced by the value in `other`.
     #[must_use]
     pub fn merged_with(mut self, other: &Self) -> Self {
+        let generation = self.generation.next();
         self.components.merge_with(
             &other.components,
-            |_key, value| Some(value.clone()),
-            |_key, mine, other| mine.merge_with(other),
+            |_key, value| {
+                Some(Entry {
+                    value: value.value.clone(),
+                    generation,
+                })
+            },
+            |_key, mine, other| {
+                if matches!(
+                    mine.value,
+                    Value::Keyword {
+                        keyword: Keyword::Revert,
+                        ..
+                    }
+                ) {
+                    mine.value = other.value.clone();
+                    mine.generation = generation;
+                } else if let Value::Component(other) = &other.value {
+                    mine.modify_component(generation, |mine| mine.merge_with(other));
+                }
+            },
         );
+        self.update_presence();
+        self.advance_to(generation);
         self
     }
 
+    /// Reconciles the changes made to `base` by `ours` and `theirs`.
+    ///
+    /// For each component, a change made by only one side is applied. When
+    /// both sides changed the same component to different values, the value
+    /// from `ours` is kept and a [`MergeConflict`] is reported.
+    #[must_use]
+    pub fn three_way_merge(base: &Self, ours: &Self, theirs: &Self) -> ThreeWayMerge {
+        Self::three_way_merge_inner(base, ours, theirs, false)
+    }
+
+    /// Reconciles the changes made to `base` by `ours` and `theirs`, merging
+    /// components changed by both sides.
+    ///
+    /// This behaves like [`Style::three_way_merge`], except that when both
+    /// sides contain different values for the same component, the value from
+    /// `ours` is [merged](StyleComponent::merge) with the value from `theirs`.
+    ///
+    /// A conflict is reported when a side removed the component, replaced it
+    /// with a [`Keyword`], or when merging left the value from `ours`
+    /// [equivalent](StyleComponent::equivalent) to what it was, which is the
+    /// case for components using the default [`StyleComponent::merge`]. The
+    /// value in `base` is only used to determine which sides changed a
+    /// component; it is not passed to the merge.
+    #[must_use]
+    pub fn three_way_merge_combined(base: &Self, ours: &Self, theirs: &Self) -> ThreeWayMerge {
+        Self::three_way_merge_inner(base, ours, theirs, true)
+    }
+
+    fn three_way_merge_inner(
+        base: &Self,
+        ours: &Self,
+        theirs: &Self,
+        combine: bool,
+    ) -> ThreeWayMerge {
+        let mut ids = base
+            .ids()
+            .chain(ours.ids())
+            .chain(theirs.ids())
+            .collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids.dedup();
+
+        let mut style = ours.clone();
+        let mut conflicts = Vec::new();
+        for id in ids {
+            let (original, mine, other) = (base.value(id), ours.value(id), theirs.value(id));
+            if equivalent(mine, other) || equivalent(original, other) {
+                // Only we changed this component, or both sides agree.
+            } else if equivalent(original, mine) {
+                style.restore_value(id, other.cloned());
+            } else if let Some(merged) = combine.then(|| combined(mine, other)).flatten() {
+                style.restore_value(id, Some(Value::Component(merged)));
+            } else {
+                conflicts.push(MergeConflict {
+                    name: id.name(),
+                    base: original.cloned().map(PatchValue),
+                    ours: mine.cloned().map(PatchValue),
+                    theirs: other.cloned().map(PatchValue),
+                });
+            }
+        }
+        ThreeWayMerge { style, conflicts }
+    }
+
     /// Returns a new [`Style`], merging the components of `self` with `other`
     /// only when the component is [`inherited`](StyleComponent::inherited).
+    ///
+    /// When both `self` and `parent` contain the same component,
+    /// [`StyleComponent::resolve_against_parent`] is invoked before the
+    /// components are merged.
+    ///
+    /// Keywords in `self` are resolved as described by [`Keyword`]. A keyword
+    /// that resolves to the parent's value is removed when `parent` does not
+    /// contain the component.
     #[must_use]
     pub fn inherited_from(mut self, parent: &Self) -> Self {
+        let generation = self.generation.next();
         self.components.merge_with(
             &parent.components,
-            |_key, value| value.inherited().then(|| value.clone()),
+            |_key, value| {
+                value.value.inherited().then(|| Entry {
+                    value: value.value.clone(),
+                    generation,
+                })
+            },
             |_key, mine, other| {
-                if other.inherited() {
-                    mine.merge_with(other);
+                if let Value::Component(other) = &other.value {
+                    mine.modify_component(generation, |mine| {
+                        mine.resolve_against_parent(other);
+                        if other.inherited() {
+                            mine.merge_with(other);
+                        }
+                    });
                 }
             },
         );
+        self.resolve_keywords(parent, generation);
+        self.update_presence();
+        self.advance_to(generation);
         self
     }
 
-    /// Returns the number of components in this style.
+    /// Resolves all keywords in this style against `parent`. Resolved entries
+    /// are marked as modified during `generation`.
+    fn resolve_keywords(&mut self, parent: &Self, generation: Generation) {
+        let keywords = self
+            .components
+            .iter()
+            .filter_map(|field| match field.value.value {
+                Value::Keyword { keyword, inherited } => Some((*field.key(), keyword, inherited)),
+                Value::Component(_) => None,
+            })
+            .collect::<Vec<_>>();
+        for (key, keyword, inherited) in keywords {
+            let inherit = match keyword {
+                Keyword::Inherit => true,
+                Keyword::Initial => continue,
+                Keyword::Unset | Keyword::Revert => inherited,
+            };
+            if inherit {
+                if let Some(parent) = parent.components.get(&key) {
+                    let entry = self.components.get_mut(&key).expect("key present");
+                    entry.value = parent.value.clone();
+                    entry.generation = generation;
+                } else {
+                    self.components.remove(&key);
+                    self.generation = generation;
+                }
+            } else {
+                let entry = self.components.get_mut(&key).expect("key present");
+                entry.value = Value::Keyword {
+                    keyword: Keyword::Initial,
+                    inherited,
+                };
+                entry.generation = generation;
+            }
+        }
+    }
+
+    /// Returns a new [`Style`] containing a value for every component in
+    /// `initial`.
+    ///
+    /// Components that are missing or that are represented by a [`Keyword`]
+    /// are replaced with their registered initial value. This is typically
+    /// called after [`Style::inherited_from`] has resolved this style's
+    /// keywords.
+    #[must_use]
+    pub fn computed(mut self, initial: &InitialValues) -> Self {
+        let generation = self.generation.next();
+        for component in initial.iter() {
+            let key = component.component_id();
+            if self
+                .components
+                .get(&key)
+                .map_or(true, |entry| entry.component().is_none())
+            {
+                self.present.insert(key);
+                self.components.insert(
+                    key,
+                    Entry {
+                        value: Value::Component(component.clone()),
+                        generation,
+                    },
+                );
+            }
+        }
+        self.advance_to(generation);
+        self
+    }
+
+    /// Converts all relative [`Dimension`](crate::Dimension)s contained in
+    /// this style's components into absolute dimensions by calling
+    /// [`StyleComponent::resolve_units`] on each component.
+    pub fn resolve_units(&mut self, context: &ResolutionContext) {
+        let generation = self.generation.next();
+        for entry in self.components.values_mut() {
+            entry.modify_component(generation, |component| component.resolve_units(context));
+        }
+        self.advance_to(generation);
+    }
+
+    /// Returns a new [`Style`] with all relative dimensions resolved, then
+    /// inherits from `parent` using [`Style::inherited_from`].
+    ///
+    /// The font size read by `font_size` from `parent` is used as the basis
+    /// for resolving the font size component of this style, and the resolved
+    /// font size of this style is used as the basis for resolving all other
+    /// [`Unit::Em`](crate::Unit::Em) dimensions. When a font size is not
+    /// available, `context.font_size` is used. `parent` is expected to have
+    /// already been resolved.
+    #[must_use]
+    pub fn resolved_inherited_from(
+        mut self,
+        parent: &Self,
+        context: &ResolutionContext,
+        font_size: &FontSizeSource,
+    ) -> Self {
+        let mut context = *context;
+        if let Some(size) = font_size.font_size(parent) {
+            context.font_size = size.to_px(&context, Axis::Vertical);
+        }
+        let generation = self.generation.next();
+        if let Some(entry) = self.components.get_mut(&font_size.component_id()) {
+            entry.modify_component(generation, |component| component.resolve_units(&context));
+            self.advance_to(generation);
+        }
+        if let Some(size) = font_size.font_size(&self) {
+            context.font_size = size.to_px(&context, Axis::Vertical);
+        }
+        self.resolve_units(&context);
+        self.inherited_from(parent)
+    }
+
+    /// Normalizes every component in this style by calling
+    /// [`DynamicComponent::normalize`].
+    pub fn normalize(&mut self) {
+        let generation = self.generation.next();
+        for entry in self.components.values_mut() {
+            entry.modify_component(generation, AnyComponent::normalize);
+        }
+        self.advance_to(generation);
+    }
+
+    /// Validates every component in this style.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`Violation`] for each component that is invalid.
+    pub fn validate(&self) -> Result<(), Vec<Violation>> {
+        let violations = self
+            .iter()
+            .filter_map(|component| {
+                component.validate().err().map(|error| Violation {
+                    name: component.name(),
+                    error,
+                })
+            })
+            .collect::<Vec<_>>();
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Replaces each [`DynamicValue`] in this style with its typed component
+    /// using the conversions registered in `registry`. Values without a
+    /// registered conversion are left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`Violation`] for each value that failed to convert. These
+    /// values remain in the style unchanged, while all other values are still
+    /// upgraded.
+    pub fn upgrade(&mut self, registry: &ConversionRegistry) -> Result<(), Vec<Violation>> {
+        let mut upgraded = Vec::new();
+        let mut violations = Vec::new();
+        for value in self.iter().filter_map(AnyComponent::get::<DynamicValue>) {
+            match registry.upgrade(value) {
+                Some(Ok(component)) => upgraded.push(component),
+                Some(Err(error)) => violations.push(Violation {
+                    name: value.name.clone(),
+                    error,
+                }),
+                None => {}
+            }
+        }
+        for component in upgraded {
+            self.push_any(component);
+        }
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Replaces each typed component in this style with a [`DynamicValue`]
+    /// using the conversions registered in `registry`. Components without a
+    /// registered conversion are left unchanged.
+    pub fn downgrade(&mut self, registry: &ConversionRegistry) {
+        let downgraded = self
+            .iter()
+            .filter_map(|component| registry.downgrade(component))
+            .collect::<Vec<_>>();
+        for value in downgraded {
+            self.push(value);
+        }
+    }
+
+    /// Rewrites every component and keyword stored under a deprecated
+    /// [alias](Name::register_alias) to use its canonical name, including the
+    /// names of [`DynamicValue`]s.
+    ///
+    /// Values are only stored under an alias if they were added before the
+    /// alias was registered. If this style also contains a value for the
+    /// canonical name, that value is kept and the aliased value is discarded.
+    pub fn canonicalize(&mut self) {
+        for id in self.ids().collect::<Vec<_>>() {
+            let Some(mut value) = self.value(id).cloned() else {
+                continue;
+            };
+            let renamed = match &mut value {
+                Value::Component(component) => component.canonicalize_name(),
+                Value::Keyword { .. } => None,
+            };
+            let name = id.name();
+            let canonical = name.canonical();
+            if canonical != name {
+                report_deprecated_alias(&name, &canonical);
+                let canonical = ComponentId::for_name(&canonical);
+                self.restore_value(id, None);
+                if self.value(canonical).is_none() {
+                    self.restore_value(canonical, Some(value));
+                }
+            } else if let Some(renamed) = renamed {
+                report_deprecated_alias(&renamed, &name);
+                self.restore_value(id, Some(value));
+            }
+        }
+    }
+
+    /// Rebuilds the set of ids of entries that contain a component.
+    fn update_presence(&mut self) {
+        self.present = self
+            .components
+            .iter()
+            .filter(|field| field.value.component().is_some())
+            .map(|field| *field.key())
+            .collect();
+    }
+
+    /// Updates this style's generation to `generation` if any entries were
+    /// modified during that generation.
+    fn advance_to(&mut self, generation: Generation) {
+        if self
+            .components
+            .values()
+            .any(|entry| entry.generation == generation)
+        {
+            self.generation = generation;
+        }
+    }
+
+    /// Returns the number of components in this style. Keywords are not
+    /// counted.
     #[must_use]
     pub fn len(&self) -> usize {
-        self.components.len()
+        self.present.len()
     }
 
-    /// Returns true if this style has no components.
+    /// Returns true if this style has no components. A style containing only
+    /// keywords is considered empty.
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.components.is_empty()
+        self.present.is_empty()
     }
 
-    /// Returns an iterator over the elements in this style.
+    /// Returns an iterator over the components in this style. Keywords are
+    /// not included.
     #[must_use]
     pub fn iter(&self) -> Iter<'_> {
         self.into_iter()
     }
+
+    /// Returns an iterator over the components in this style whose names
+    /// [start with](Name::starts_with) `prefix`.
+    #[must_use]
+    pub fn iter_prefixed<'a>(&'a self, prefix: &'a Name) -> Prefixed<'a> {
+        Prefixed {
+            components: self.iter(),
+            prefix,
+        }
+    }
+
+    /// Returns an iterator over the components in this style whose names have
+    /// the [authority](Name::authority) `authority`.
+    #[must_use]
+    pub fn authority(&self, authority: &Identifier) -> ByAuthority<'_> {
+        ByAuthority {
+            entries: self.components.iter(),
+            ids: ComponentSet::for_authority(authority),
+        }
+    }
+
+    /// Returns a view of this style that only contains components whose names
+    /// have the [authority](Name::authority) `authority`.
+    #[must_use]
+    pub fn scoped(&self, authority: Identifier) -> ScopedStyle<'_> {
+        ScopedStyle::new(self, authority)
+    }
+
+    /// Returns a separate style for each [authority](Name::authority) of the
+    /// components and keywords in this style.
+    #[must_use]
+    pub fn split_by_authority(&self) -> Map<Identifier, Style> {
+        let mut styles = Map::<Identifier, Style>::new();
+        for (authority, ids) in self.ids().collect::<ComponentSet>().by_authority() {
+            styles.insert(authority, self.with_ids(&ids));
+        }
+        styles
+    }
+
+    /// Returns a copy of the components and keywords in this style whose
+    /// names have the [authority](Name::authority) `authority`.
+    pub(crate) fn with_authority(&self, authority: &Identifier) -> Style {
+        self.with_ids(&ComponentSet::for_authority(authority))
+    }
+
+    /// Returns a copy of the components and keywords in this style whose ids
+    /// are contained in `ids`.
+    fn with_ids(&self, ids: &ComponentSet) -> Style {
+        let mut style = Style::new();
+        for field in &self.components {
+            if ids.contains(*field.key()) {
+                style.restore_value(*field.key(), Some(field.value.value.clone()));
+            }
+        }
+        style
+    }
 }
 
 impl<'a> IntoIterator for &'a Style {
@@ -140,22 +895,125 @@ impl IntoIterator for Style {
 }
 
 /// An iterator over the components contained in a [`Style`].
-pub struct Iter<'a>(kempt::map::Values<'a, NameKey<'static>, AnyComponent>);
+pub struct Iter<'a>(kempt::map::Values<'a, ComponentId, Entry>);
 
 impl<'a> Iterator for Iter<'a> {
     type Item = &'a AnyComponent;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.next()
+        self.0.find_map(Entry::component)
     }
 }
 
-pub struct IntoIter(kempt::map::IntoValues<NameKey<'static>, AnyComponent>);
+pub struct IntoIter(kempt::map::IntoValues<ComponentId, Entry>);
 
 impl Iterator for IntoIter {
     type Item = AnyComponent;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.next()
+        self.0.find_map(|entry| match entry.value {
+            Value::Component(component) => Some(component),
+            Value::Keyword { .. } => None,
+        })
+    }
+}
+
+/// An iterator over the components of a [`Style`] whose names start with a
+/// prefix.
+///
+/// See [`Style::iter_prefixed`].
+pub struct Prefixed<'a> {
+    components: Iter<'a>,
+    prefix: &'a Name,
+}
+
+impl<'a> Iterator for Prefixed<'a> {
+    type Item = &'a AnyComponent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let prefix = self.prefix;
+        self.components
+            .find(|component| component.name().starts_with(prefix))
+    }
+}
+
+/// An iterator over the components of a [`Style`] whose names have a specific
+/// authority.
+///
+/// See [`Style::authority`].
+pub struct ByAuthority<'a> {
+    entries: kempt::map::Iter<'a, ComponentId, Entry>,
+    ids: ComponentSet,
+}
+
+impl<'a> Iterator for ByAuthority<'a> {
+    type Item = &'a AnyComponent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let ids = &self.ids;
+        self.entries.find_map(|field| {
+            let component = field.value.component()?;
+            ids.contains(*field.key()).then_some(component)
+        })
+    }
+}
+
+/// An iterator over the components of a [`Style`] that were modified after a
+/// given [`Generation`].
+pub struct ChangedSince<'a> {
+    entries: kempt::map::Values<'a, ComponentId, Entry>,
+    generation: Generation,
+}
+
+impl<'a> Iterator for ChangedSince<'a> {
+    type Item = &'a AnyComponent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries
+            .by_ref()
+            .filter(|entry| entry.generation > self.generation)
+            .find_map(Entry::component)
     }
 }
+
+/// A counter identifying a revision of a [`Style`].
+///
+/// See [`Style::generation()`] and [`Style::component_generation()`].
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Generation(u64);
+
+impl Generation {
+    /// Returns the generation following this one.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        Self(self.0.wrapping_add(1))
+    }
+
+    /// Returns the numerical value of this generation.
+    #[must_use]
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+}
+
+/// A keyword that can be stored in a [`Style`] in place of a component's
+/// value, overriding the component's default inheritance behavior.
+///
+/// See [`Style::push_keyword`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Keyword {
+    /// Uses the parent's value, even if the component is not
+    /// [inherited](StyleComponent::inherited).
+    Inherit,
+    /// Uses the component's initial value, preventing the parent's value from
+    /// being inherited.
+    Initial,
+    /// Behaves as [`Keyword::Inherit`] if the component is
+    /// [inherited](StyleComponent::inherited), otherwise behaves as
+    /// [`Keyword::Initial`].
+    Unset,
+    /// Discards this style's value in favor of the value in the style it is
+    /// merged with by [`Style::merged_with`]. If no value is present after
+    /// merging, this behaves as [`Keyword::Unset`] when inheriting.
+    Revert,
+}
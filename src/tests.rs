@@ -1,4 +1,12 @@
-use crate::{Name, Style, StyleComponent};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::{
+    static_name, AnyComponent, Axis, ChangeKind, ComponentId, ComponentSet, ConversionRegistry,
+    Dimension, DynamicValue, FontSizeSource, Identifier, InitialValues, InvalidValue, Keyword,
+    MergeStrategy, Name, ObservableStyle, Operation, PatchValue, ResolutionContext, ScriptValue,
+    StaticName, Style, StyleComponent, StyleHistory, StylePatch, StyleTree, StyleWorld, Violation,
+};
 
 #[derive(Default, Debug, Clone, Eq, PartialEq)]
 pub struct FontSize(u32);
@@ -7,12 +15,60 @@ impl StyleComponent for FontSize {
     fn inherited() -> bool {
         true
     }
+
+    fn initial_value() -> Option<Self> {
+        Some(FontSize(12))
+    }
+
+    fn equivalent(&self, other: &Self) -> bool {
+        self == other
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct NotInheritable;
 
-impl StyleComponent for NotInheritable {}
+impl StyleComponent for NotInheritable {
+    fn equivalent(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Opacity {
+    Absolute(f32),
+    Multiply(f32),
+}
+
+impl StyleComponent for Opacity {
+    fn normalize(&mut self) {
+        if let Opacity::Absolute(opacity) = self {
+            *opacity = opacity.clamp(0., 1.);
+        }
+    }
+
+    fn validate(&self) -> Result<(), InvalidValue> {
+        match self {
+            Opacity::Absolute(opacity) if !(0. ..=1.).contains(opacity) => {
+                Err(InvalidValue::new("opacity must be between 0 and 1"))
+            }
+            Opacity::Multiply(factor) if *factor < 0. => {
+                Err(InvalidValue::new("factor must not be negative"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn resolve_against_parent(&mut self, parent: &Self) {
+        if let (Opacity::Multiply(factor), Opacity::Absolute(parent)) = (*self, parent) {
+            *self = Opacity::Absolute(parent * factor);
+        }
+    }
+
+    fn equivalent(&self, other: &Self) -> bool {
+        self == other
+    }
+}
 
 #[test]
 fn basics() {
@@ -47,3 +103,1134 @@ fn names() {
         Name::private("not_inheritable").unwrap()
     );
 }
+
+#[test]
+fn relative_inheritance() {
+    let parent = Style::new().with(Opacity::Absolute(0.5));
+    let child = Style::new()
+        .with(Opacity::Multiply(0.5))
+        .inherited_from(&parent);
+    assert_eq!(child.get::<Opacity>(), Some(&Opacity::Absolute(0.25)));
+    let grandchild = Style::new()
+        .with(Opacity::Multiply(0.5))
+        .inherited_from(&child);
+    assert_eq!(grandchild.get::<Opacity>(), Some(&Opacity::Absolute(0.125)));
+    // Opacity is not inherited, so a child without a value is unaffected.
+    assert_eq!(
+        Style::new().inherited_from(&grandchild).get::<Opacity>(),
+        None
+    );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Indent(Dimension);
+
+impl StyleComponent for Indent {
+    fn inherited() -> bool {
+        true
+    }
+
+    fn resolve_units(&mut self, context: &ResolutionContext) {
+        self.0 = self.0.to_absolute(context, Axis::Horizontal);
+    }
+
+    fn equivalent(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+#[test]
+fn font_size_source() {
+    // Any component can provide the basis for em units.
+    let source = FontSizeSource::new(|indent: &Indent| indent.0);
+    let context = ResolutionContext {
+        font_size: 10.,
+        ..ResolutionContext::default()
+    };
+    let parent = Style::new().with(Indent(Dimension::px(20.)));
+    let mut child = Style::new()
+        .with(Indent(Dimension::em(2.)))
+        .resolved_inherited_from(&parent, &context, &source);
+    assert_eq!(child.get::<Indent>(), Some(&Indent(Dimension::px(40.))));
+
+    let resolved = child.generation();
+    child.resolve_units(&context);
+    assert_eq!(child.generation(), resolved);
+}
+
+#[test]
+fn keywords() {
+    let parent = Style::new().with(FontSize(1)).with(NotInheritable);
+
+    let inherit = Style::new()
+        .with_keyword::<NotInheritable>(Keyword::Inherit)
+        .inherited_from(&parent);
+    assert_eq!(inherit.get::<NotInheritable>(), Some(&NotInheritable));
+    let missing = Style::new()
+        .with_keyword::<NotInheritable>(Keyword::Inherit)
+        .inherited_from(&Style::new());
+    assert!(missing.is_empty());
+
+    let initial = Style::new()
+        .with_keyword::<FontSize>(Keyword::Initial)
+        .inherited_from(&parent);
+    assert_eq!(initial.get::<FontSize>(), None);
+    assert_eq!(initial.keyword(&FontSize::name()), Some(Keyword::Initial));
+    // The keyword is inherited by descendants.
+    let grandchild = Style::new().inherited_from(&initial);
+    assert_eq!(grandchild.get::<FontSize>(), None);
+
+    let unset = Style::new()
+        .with_keyword::<FontSize>(Keyword::Unset)
+        .with_keyword::<NotInheritable>(Keyword::Unset)
+        .inherited_from(&parent);
+    assert_eq!(unset.get::<FontSize>(), Some(&FontSize(1)));
+    assert_eq!(unset.get::<NotInheritable>(), None);
+    assert_eq!(
+        unset.keyword(&NotInheritable::name()),
+        Some(Keyword::Initial)
+    );
+
+    let overrides = Style::new()
+        .with_keyword::<FontSize>(Keyword::Revert)
+        .with_keyword::<NotInheritable>(Keyword::Initial);
+    let merged = overrides.merged_with(&parent);
+    assert_eq!(merged.get::<FontSize>(), Some(&FontSize(1)));
+    assert_eq!(merged.get::<NotInheritable>(), None);
+    assert_eq!(merged.iter().count(), 1);
+    assert_eq!(merged.len(), 1);
+    assert_eq!(
+        merged.keyword(&NotInheritable::name()),
+        Some(Keyword::Initial)
+    );
+    let mut removed = merged.clone();
+    assert_eq!(removed.remove::<NotInheritable>(), None);
+    assert_eq!(removed.keyword(&NotInheritable::name()), None);
+    assert!(Style::new()
+        .with_keyword::<FontSize>(Keyword::Inherit)
+        .is_empty());
+    assert_eq!(
+        format!(
+            "{:?}",
+            Style::new().with_keyword::<NotInheritable>(Keyword::Initial)
+        ),
+        "Style(not_inheritable: Initial)"
+    );
+}
+
+#[test]
+fn initial_values() {
+    let initial = InitialValues::new()
+        .registered::<FontSize>()
+        .with(Opacity::Absolute(1.));
+    assert!(!InitialValues::new().register::<NotInheritable>());
+    assert_eq!(initial.len(), 2);
+    assert_eq!(initial.get::<FontSize>(), Some(&FontSize(12)));
+
+    let computed = Style::new().with(NotInheritable).computed(&initial);
+    assert_eq!(computed.get::<FontSize>(), Some(&FontSize(12)));
+    assert_eq!(computed.get::<Opacity>(), Some(&Opacity::Absolute(1.)));
+    assert_eq!(computed.get::<NotInheritable>(), Some(&NotInheritable));
+
+    let parent = Style::new().with(FontSize(1));
+    let computed = Style::new()
+        .with(Opacity::Absolute(0.5))
+        .inherited_from(&parent)
+        .computed(&initial);
+    assert_eq!(computed.get::<FontSize>(), Some(&FontSize(1)));
+    assert_eq!(computed.get::<Opacity>(), Some(&Opacity::Absolute(0.5)));
+
+    let reset = Style::new()
+        .with_keyword::<FontSize>(Keyword::Initial)
+        .inherited_from(&parent)
+        .computed(&initial);
+    assert_eq!(reset.get::<FontSize>(), Some(&FontSize(12)));
+    assert_eq!(reset.keyword(&FontSize::name()), None);
+}
+
+#[test]
+fn validation() {
+    let mut style = Style::new().with_checked(Opacity::Absolute(2.)).unwrap();
+    assert_eq!(style.get::<Opacity>(), Some(&Opacity::Absolute(1.)));
+    assert_eq!(
+        style.push_checked(Opacity::Multiply(-1.)),
+        Err(Violation {
+            name: Opacity::name(),
+            error: InvalidValue::new("factor must not be negative"),
+        })
+    );
+    assert_eq!(style.get::<Opacity>(), Some(&Opacity::Absolute(1.)));
+    assert_eq!(style.validate(), Ok(()));
+
+    style.push(Opacity::Absolute(-1.));
+    let violations = style.validate().unwrap_err();
+    assert_eq!(violations.len(), 1);
+    assert_eq!(
+        violations[0].to_string(),
+        "opacity: opacity must be between 0 and 1"
+    );
+    style.normalize();
+    assert_eq!(style.get::<Opacity>(), Some(&Opacity::Absolute(0.)));
+    assert_eq!(style.validate(), Ok(()));
+    let normalized = style.generation();
+    style.normalize();
+    assert_eq!(style.generation(), normalized);
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ButtonPadding(u32);
+
+impl StyleComponent for ButtonPadding {
+    fn name() -> Name {
+        Name::with_path("gooey", ["button", "padding"]).unwrap()
+    }
+
+    fn equivalent(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ButtonMargin(u32);
+
+impl StyleComponent for ButtonMargin {
+    fn name() -> Name {
+        static NAME: StaticName = static_name!("gooey", ["button"], "margin");
+        NAME.to_name()
+    }
+
+    fn equivalent(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+#[test]
+fn prefixes() {
+    let style = Style::new()
+        .with(ButtonPadding(1))
+        .with(ButtonMargin(2))
+        .with(FontSize(3));
+    assert_eq!(style.get::<ButtonPadding>(), Some(&ButtonPadding(1)));
+    let button = Name::new("gooey", "button").unwrap();
+    let mut names = style
+        .iter_prefixed(&button)
+        .map(|component| component.name().to_string())
+        .collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, ["gooey::button::margin", "gooey::button::padding"]);
+    assert_eq!(
+        ButtonMargin::name(),
+        Name::with_path("gooey", ["button", "margin"]).unwrap()
+    );
+    assert_eq!(style.iter_prefixed(&ButtonMargin::name()).count(), 1);
+    assert_eq!(style.iter_prefixed(&FontSize::name()).count(), 1);
+}
+
+#[test]
+fn component_ids() {
+    let id = FontSize::component_id();
+    assert_eq!(id, ComponentId::of::<FontSize>());
+    assert_eq!(id, ComponentId::for_name(&FontSize::name()));
+    assert_eq!(ComponentId::find(&FontSize::name()), Some(id));
+    assert_eq!(id.name(), FontSize::name());
+    assert_ne!(id, NotInheritable::component_id());
+    assert_eq!(
+        ComponentId::find(&Name::private("never_used").unwrap()),
+        None
+    );
+
+    let mut style = Style::new().with(FontSize(1));
+    assert_eq!(
+        style.get_by_id(id).and_then(AnyComponent::get),
+        Some(&FontSize(1))
+    );
+    assert!(style
+        .get_by_name(&Name::private("never_used").unwrap())
+        .is_none());
+    assert!(style.remove_by_id(id).is_some());
+    assert!(style.is_empty());
+}
+
+#[test]
+fn component_sets() {
+    let mut set = ComponentSet::new().with::<FontSize>();
+    assert!(set.contains(FontSize::component_id()));
+    assert!(!set.contains(NotInheritable::component_id()));
+    assert!(set.insert(NotInheritable::component_id()));
+    assert!(!set.insert(NotInheritable::component_id()));
+    assert_eq!(set.len(), 2);
+    assert!(set.remove(NotInheritable::component_id()));
+    assert!(!set.remove(NotInheritable::component_id()));
+    assert_eq!(set, ComponentSet::new().with::<FontSize>());
+    assert_eq!(set.iter().collect::<Vec<_>>(), [FontSize::component_id()]);
+
+    let style = Style::new().with(FontSize(1)).with(Opacity::Absolute(1.));
+    assert!(style.contains::<FontSize>());
+    assert!(!style.contains::<NotInheritable>());
+    assert!(style.contains_name(&FontSize::name()));
+    assert!(!style.contains_name(&Name::private("never_used").unwrap()));
+    let either = ComponentSet::new()
+        .with::<FontSize>()
+        .with::<NotInheritable>();
+    assert!(style.contains_any(&either));
+    assert!(!style.contains_all(&either));
+    assert!(style.contains_all(&ComponentSet::new().with::<FontSize>().with::<Opacity>()));
+    assert!(style.contains_all(&ComponentSet::new()));
+    assert!(!style.contains_any(&ComponentSet::new()));
+
+    // Keywords are not considered present, and presence is kept up to date by
+    // operations that combine styles.
+    let style = Style::new()
+        .with(NotInheritable)
+        .with_keyword::<FontSize>(Keyword::Inherit);
+    assert!(!style.contains::<FontSize>());
+    let inherited = style.inherited_from(&Style::new().with(FontSize(2)));
+    assert!(inherited.contains::<FontSize>());
+    assert_eq!(inherited.component_set(), &either);
+    let mut merged = Style::new().merged_with(&inherited);
+    assert!(merged.contains_all(&either));
+    merged.remove::<FontSize>();
+    assert!(!merged.contains::<FontSize>());
+}
+
+#[test]
+fn generations() {
+    let style = Style::new().with(FontSize(1));
+    let first = style.generation();
+    assert_eq!(style.component_generation(&FontSize::name()), Some(first));
+
+    let style = style.with(NotInheritable);
+    assert!(style.generation() > first);
+    let changed = style.changed_since(first).collect::<Vec<_>>();
+    assert_eq!(changed.len(), 1);
+    assert_eq!(changed[0].get::<NotInheritable>(), Some(&NotInheritable));
+
+    let unchanged = style.generation();
+    let style = style.inherited_from(&Style::new().with(NotInheritable));
+    assert_eq!(style.generation(), unchanged);
+    // FontSize doesn't merge, so merging another value doesn't modify it.
+    let style = style.merged_with(&Style::new().with(FontSize(2)));
+    assert_eq!(style.generation(), unchanged);
+    let style = style
+        .with(Layers(1))
+        .merged_with(&Style::new().with(Layers(2)));
+    assert!(style.generation() > unchanged);
+    assert_eq!(
+        style.component_generation(&Layers::name()),
+        Some(style.generation())
+    );
+    assert_eq!(style.component_generation(&FontSize::name()), Some(first));
+
+    let mut style = style;
+    let before_removal = style.generation();
+    assert_eq!(style.remove::<FontSize>(), Some(FontSize(1)));
+    assert!(style.generation() > before_removal);
+    assert_eq!(style.changed_since(before_removal).count(), 0);
+}
+
+#[test]
+fn observable() {
+    let observable = ObservableStyle::new(Style::new().with(NotInheritable).with(Layers(1)));
+    let font_sizes = observable.subscribe::<FontSize>();
+    let layers = observable.subscribe::<Layers>();
+    let removals = Arc::new(AtomicUsize::new(0));
+    observable.on_change::<NotInheritable>({
+        let removals = removals.clone();
+        move |change| {
+            assert_eq!(change.kind, ChangeKind::Removed);
+            removals.fetch_add(1, Ordering::Relaxed);
+        }
+    });
+
+    observable.push(FontSize(1));
+    std::thread::spawn({
+        let observable = observable.clone();
+        move || observable.merge(&Style::new().with(FontSize(2)).with(Layers(2)))
+    })
+    .join()
+    .unwrap();
+    assert_eq!(observable.remove::<NotInheritable>(), Some(NotInheritable));
+    assert_eq!(observable.remove::<NotInheritable>(), None);
+    assert_eq!(removals.load(Ordering::Relaxed), 1);
+
+    // FontSize doesn't merge, so merging FontSize(2) leaves the value as-is.
+    let changes = font_sizes.drain();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].kind, ChangeKind::Inserted);
+    let changes = layers.drain();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].kind, ChangeKind::Merged);
+    assert_eq!(
+        changes[0]
+            .component
+            .as_ref()
+            .and_then(|component| component.get::<Layers>()),
+        Some(&Layers(3))
+    );
+
+    // Replacing with an identical value is not a change.
+    observable.replace(Style::new().with(FontSize(1)).with(Layers(4)));
+    assert!(font_sizes.is_empty());
+    assert_eq!(layers.drain()[0].kind, ChangeKind::Replaced);
+    assert!(font_sizes.is_empty());
+    observable.push(FontSize(1));
+    assert!(font_sizes.is_empty());
+
+    // Keywords are treated as values of their component.
+    let keywords = ObservableStyle::new(Style::new().with_keyword::<FontSize>(Keyword::Initial));
+    let font_sizes = keywords.subscribe::<FontSize>();
+    assert!(keywords.remove_by_name(&FontSize::name()).is_none());
+    keywords.push(FontSize(1));
+    let kinds = font_sizes
+        .drain()
+        .into_iter()
+        .map(|change| change.kind)
+        .collect::<Vec<_>>();
+    assert_eq!(kinds, [ChangeKind::Removed, ChangeKind::Inserted]);
+}
+
+#[test]
+fn world() {
+    let mut world = StyleWorld::new();
+    let a = world
+        .spawn_style(&Style::new().with(FontSize(1)).with(NotInheritable))
+        .unwrap();
+    let b = world.spawn();
+    assert_eq!(world.insert(b, FontSize(2)), Ok(true));
+    let c = world.spawn_style(&Style::new().with(FontSize(3))).unwrap();
+    assert_eq!(world.len(), 3);
+
+    // A dynamic value sharing a typed component's name cannot be stored in
+    // the typed column.
+    let dynamic = DynamicValue::new(FontSize::name(), 4_i64);
+    let mismatch = world.insert(b, dynamic.clone()).unwrap_err();
+    assert_eq!(mismatch.name, FontSize::name());
+    assert!(world
+        .spawn_style(&Style::new().with(NotInheritable).with(dynamic))
+        .is_err());
+    assert_eq!(world.len(), 3);
+    assert_eq!(world.column::<NotInheritable>().unwrap().len(), 1);
+
+    let column = world.column::<FontSize>().unwrap();
+    assert_eq!(column.entities(), &[a, b, c]);
+    assert_eq!(column.values(), &[FontSize(1), FontSize(2), FontSize(3)]);
+
+    for size in world.column_mut::<FontSize>().unwrap().values_mut() {
+        size.0 *= 10;
+    }
+    assert_eq!(world.get::<FontSize>(b), Some(&FontSize(20)));
+    assert_eq!(
+        world
+            .get_by_name(a, &NotInheritable::name())
+            .and_then(|component| component.get::<NotInheritable>().cloned()),
+        Some(NotInheritable)
+    );
+
+    // Despawning moves the last value into the vacated slot.
+    assert!(world.despawn(a));
+    assert!(!world.despawn(a));
+    assert!(!world.contains(a));
+    assert_eq!(world.entities(), &[c, b]);
+    assert_eq!(world.column::<FontSize>().unwrap().entities(), &[c, b]);
+    assert_eq!(world.get::<FontSize>(c), Some(&FontSize(30)));
+    assert_eq!(world.column::<NotInheritable>().unwrap().len(), 0);
+
+    assert_eq!(world.remove::<FontSize>(c), Some(FontSize(30)));
+    assert_eq!(world.get::<FontSize>(b), Some(&FontSize(20)));
+    let style = world.style(c).unwrap();
+    assert!(style.is_empty());
+    let style = world.style(b).unwrap();
+    assert_eq!(style.get::<FontSize>(), Some(&FontSize(20)));
+    assert!(world.style(a).is_none());
+}
+
+#[test]
+fn queries() {
+    let mut world = StyleWorld::new();
+    let a = world
+        .spawn_style(&Style::new().with(FontSize(1)).with(NotInheritable))
+        .unwrap();
+    let b = world
+        .spawn_style(&Style::new().with(FontSize(2)).with(Opacity::Absolute(0.5)))
+        .unwrap();
+    let c = world
+        .spawn_style(&Style::new().with(Opacity::Absolute(1.)))
+        .unwrap();
+
+    let sizes = world
+        .query::<(&FontSize, Option<&Opacity>)>()
+        .map(|(entity, (size, opacity))| (entity, size.0, opacity.copied()))
+        .collect::<Vec<_>>();
+    assert_eq!(sizes, [(a, 1, None), (b, 2, Some(Opacity::Absolute(0.5)))]);
+    let filtered = world
+        .query::<&FontSize>()
+        .without::<NotInheritable>()
+        .map(|(entity, _)| entity)
+        .collect::<Vec<_>>();
+    assert_eq!(filtered, [b]);
+
+    world
+        .query_mut::<(&mut Opacity, Option<&FontSize>)>()
+        .for_each(|_, (opacity, size)| {
+            if let (Opacity::Absolute(opacity), Some(FontSize(2))) = (opacity, size) {
+                *opacity *= 2.;
+            }
+        });
+    assert_eq!(world.get::<Opacity>(b), Some(&Opacity::Absolute(1.)));
+    assert_eq!(world.get::<Opacity>(c), Some(&Opacity::Absolute(1.)));
+
+    let query = world.query_mut::<&mut FontSize>().with::<NotInheritable>();
+    assert_eq!(query.entities(), [a]);
+    query.for_each(|_, size| size.0 += 10);
+    assert_eq!(world.get::<FontSize>(a), Some(&FontSize(11)));
+    assert_eq!(world.get::<FontSize>(b), Some(&FontSize(2)));
+}
+
+#[test]
+#[should_panic = "accessed mutably"]
+fn conflicting_queries() {
+    let mut world = StyleWorld::new();
+    let _ = world.query_mut::<(&mut FontSize, &FontSize)>();
+}
+
+#[test]
+#[should_panic = "more than once"]
+fn duplicate_queries() {
+    let world = StyleWorld::new();
+    let _ = world.query::<(&FontSize, Option<&FontSize>)>();
+}
+
+#[test]
+fn trees() {
+    let mut tree = StyleTree::new();
+    let root = tree.push_root(
+        Style::new()
+            .with(FontSize(1))
+            .with(NotInheritable)
+            .with(Opacity::Absolute(0.5)),
+    );
+    let mut leaves = Vec::new();
+    for size in 0..8 {
+        let child = tree.push_child(root, Style::new().with(Opacity::Multiply(0.5)));
+        let leaf = tree.push_child(
+            child,
+            Style::new()
+                .with(FontSize(size))
+                .with_keyword::<Opacity>(Keyword::Inherit),
+        );
+        leaves.push((child, leaf, size));
+    }
+    assert_eq!(tree.len(), 17);
+    assert_eq!(tree.children(root).len(), 8);
+
+    let resolved = tree.resolve();
+    for (child, leaf, size) in &leaves {
+        assert_eq!(tree.parent(*leaf), Some(*child));
+        let child = &resolved[child.index()];
+        assert_eq!(child.get::<FontSize>(), Some(&FontSize(1)));
+        assert_eq!(child.get::<Opacity>(), Some(&Opacity::Absolute(0.25)));
+        assert!(child.get::<NotInheritable>().is_none());
+        let leaf = &resolved[leaf.index()];
+        assert_eq!(leaf.get::<FontSize>(), Some(&FontSize(*size)));
+        assert_eq!(leaf.get::<Opacity>(), Some(&Opacity::Absolute(0.25)));
+    }
+
+    #[cfg(feature = "rayon")]
+    assert_eq!(format!("{:?}", tree.par_resolve()), format!("{resolved:?}"));
+}
+
+#[test]
+fn history() {
+    let mut history = StyleHistory::new(Style::new().with(FontSize(1)));
+    let start = history.checkpoint();
+    assert!(!history.can_undo());
+
+    history.push(FontSize(2));
+    history.transaction(|history| {
+        history.push(NotInheritable);
+        history.merge(
+            &Style::new()
+                .with(FontSize(3))
+                .with_keyword::<Opacity>(Keyword::Initial),
+        );
+    });
+    let edited = history.checkpoint();
+    assert_eq!(history.style().get::<FontSize>(), Some(&FontSize(2)));
+    assert_eq!(
+        history.style().get::<NotInheritable>(),
+        Some(&NotInheritable)
+    );
+    assert_eq!(
+        history.style().keyword(&Opacity::name()),
+        Some(Keyword::Initial)
+    );
+
+    assert!(history.undo());
+    assert!(history.style().get::<NotInheritable>().is_none());
+    assert!(history.style().keyword(&Opacity::name()).is_none());
+    assert_eq!(history.style().get::<FontSize>(), Some(&FontSize(2)));
+    assert!(history.redo());
+    assert_eq!(
+        history.style().get::<NotInheritable>(),
+        Some(&NotInheritable)
+    );
+    assert!(!history.redo());
+
+    assert_eq!(history.remove::<FontSize>(), Some(FontSize(2)));
+    assert_eq!(history.remove::<FontSize>(), None);
+    assert!(history.revert_to(start));
+    assert_eq!(history.style().get::<FontSize>(), Some(&FontSize(1)));
+    assert_eq!(history.style().len(), 1);
+    assert!(history.revert_to(edited));
+    assert_eq!(history.style().get::<FontSize>(), Some(&FontSize(2)));
+    assert_eq!(
+        history.style().get::<NotInheritable>(),
+        Some(&NotInheritable)
+    );
+
+    // New edits discard the steps that were undone.
+    assert!(history.undo());
+    history.push(FontSize(4));
+    assert!(!history.can_redo());
+    assert!(!history.revert_to(edited));
+}
+
+#[test]
+fn history_skips_unchanged_edits() {
+    let mut history = StyleHistory::new(Style::new().with(FontSize(1)));
+    history.push(FontSize(1));
+    history.merge(&Style::new().with(FontSize(1)));
+    assert!(!history.can_undo());
+
+    let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        history.transaction(|history| {
+            history.push(NotInheritable);
+            panic!("transaction failed");
+        });
+    }));
+    assert!(panicked.is_err());
+    history.push(FontSize(2));
+
+    // The edits made before the panic are a separate step.
+    assert!(history.undo());
+    assert_eq!(history.style().get::<FontSize>(), Some(&FontSize(1)));
+    assert!(history.style().get::<NotInheritable>().is_some());
+    assert!(history.undo());
+    assert!(history.style().get::<NotInheritable>().is_none());
+    assert!(!history.can_undo());
+}
+
+#[test]
+fn patches() {
+    let base = Style::new().with(FontSize(1)).with(NotInheritable);
+    let edited = Style::new()
+        .with(FontSize(2))
+        .with_keyword::<Opacity>(Keyword::Inherit);
+    let patch = StylePatch::diff(&base, &edited);
+    assert_eq!(patch.len(), 3);
+    assert!(StylePatch::diff(&base, &base.clone()).is_empty());
+
+    let mut style = base.clone();
+    patch.apply(&mut style).unwrap();
+    assert_eq!(style.get::<FontSize>(), Some(&FontSize(2)));
+    assert!(style.get::<NotInheritable>().is_none());
+    assert_eq!(style.keyword(&Opacity::name()), Some(Keyword::Inherit));
+    assert!(StylePatch::diff(&style, &edited).is_empty());
+
+    // Applying the patch again conflicts with the changes it already made.
+    let conflicts = patch.apply(&mut style).unwrap_err();
+    assert_eq!(conflicts.len(), 3);
+    let conflict = conflicts
+        .iter()
+        .find(|conflict| conflict.name == FontSize::name())
+        .unwrap();
+    assert_eq!(conflict.expected, Some(PatchValue::component(FontSize(1))));
+    assert_eq!(conflict.found, Some(PatchValue::component(FontSize(2))));
+    assert_eq!(style.get::<FontSize>(), Some(&FontSize(2)));
+
+    patch.inverted().apply(&mut style).unwrap();
+    assert!(StylePatch::diff(&style, &base).is_empty());
+
+    let grow = StylePatch::new().with(Operation::set(
+        Some(PatchValue::component(FontSize(2))),
+        FontSize(3),
+    ));
+    let combined = patch.composed_with(&grow).unwrap();
+    assert_eq!(combined.len(), 3);
+    let mut style = base.clone();
+    combined.apply(&mut style).unwrap();
+    assert_eq!(style.get::<FontSize>(), Some(&FontSize(3)));
+    assert!(patch.composed_with(&patch.inverted()).unwrap().is_empty());
+    assert_eq!(grow.composed_with(&grow).unwrap_err().len(), 1);
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Layers(u32);
+
+impl StyleComponent for Layers {
+    fn merge(&mut self, other: &Self) {
+        self.0 += other.0;
+    }
+
+    fn equivalent(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+#[test]
+fn three_way_merges() {
+    let base = Style::new()
+        .with(FontSize(1))
+        .with(Layers(1))
+        .with(Opacity::Absolute(1.));
+    let ours = Style::new()
+        .with(FontSize(2))
+        .with(Layers(2))
+        .with(Opacity::Absolute(1.))
+        .with(NotInheritable);
+    let theirs = Style::new()
+        .with(FontSize(1))
+        .with(Layers(3))
+        .with_keyword::<Opacity>(Keyword::Initial);
+
+    let merged = Style::three_way_merge(&base, &ours, &theirs);
+    assert_eq!(merged.style.get::<FontSize>(), Some(&FontSize(2)));
+    assert_eq!(merged.style.get::<NotInheritable>(), Some(&NotInheritable));
+    assert_eq!(
+        merged.style.keyword(&Opacity::name()),
+        Some(Keyword::Initial)
+    );
+    assert_eq!(merged.conflicts.len(), 1);
+    let conflict = &merged.conflicts[0];
+    assert_eq!(conflict.name, Layers::name());
+    assert_eq!(conflict.base, Some(PatchValue::component(Layers(1))));
+    assert_eq!(conflict.theirs, Some(PatchValue::component(Layers(3))));
+    assert_eq!(merged.style.get::<Layers>(), Some(&Layers(2)));
+
+    // Combining merges both sides without considering the base value.
+    let combined = Style::three_way_merge_combined(&base, &ours, &theirs);
+    assert!(combined.conflicts.is_empty());
+    assert_eq!(combined.style.get::<Layers>(), Some(&Layers(5)));
+
+    // Components whose merge keeps our value can't be combined.
+    let resized = Style::new().with(FontSize(3)).with(Layers(1));
+    let merged = Style::three_way_merge_combined(&base, &ours, &resized);
+    assert_eq!(merged.conflicts.len(), 1);
+    assert_eq!(merged.conflicts[0].name, FontSize::name());
+    assert_eq!(
+        merged.conflicts[0].theirs,
+        Some(PatchValue::component(FontSize(3)))
+    );
+    assert_eq!(merged.style.get::<FontSize>(), Some(&FontSize(2)));
+
+    // Removing a component that the other side edited conflicts.
+    let removed = Style::new().with(FontSize(1)).with(Opacity::Absolute(1.));
+    let merged = Style::three_way_merge_combined(&base, &ours, &removed);
+    assert_eq!(merged.conflicts.len(), 1);
+    assert_eq!(merged.conflicts[0].name, Layers::name());
+    assert!(merged.conflicts[0].theirs.is_none());
+}
+
+#[cfg(feature = "hot-reload")]
+#[test]
+fn hot_reload() {
+    use crate::{ReloadEvent, StyleWatcher};
+
+    fn parse(source: &str) -> Result<Style, String> {
+        let mut style = Style::new();
+        for line in source.lines() {
+            match line.split_once('=') {
+                Some(("font_size", size)) => {
+                    style.push(FontSize(size.parse().map_err(|_| "invalid size")?));
+                }
+                None if line == "not_inheritable" => style.push(NotInheritable),
+                _ => return Err(format!("unexpected line: {line}")),
+            }
+        }
+        Ok(style)
+    }
+
+    let dir = std::env::temp_dir().join(format!("stylecs-hot-reload-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let base = dir.join("base.style");
+    let theme = dir.join("theme.style");
+    std::fs::write(&base, "font_size=1\nnot_inheritable").unwrap();
+    std::fs::write(&theme, "font_size=2").unwrap();
+
+    let watcher = StyleWatcher::new([&base, &theme], parse).unwrap();
+    assert_eq!(watcher.style().get::<FontSize>(), Some(&FontSize(2)));
+    assert_eq!(
+        watcher.style().get::<NotInheritable>(),
+        Some(&NotInheritable)
+    );
+    assert!(watcher.check().unwrap().is_none());
+    let font_sizes = watcher.observable().subscribe::<FontSize>();
+    let failures = Arc::new(AtomicUsize::new(0));
+    watcher.on_reload({
+        let failures = failures.clone();
+        move |event| {
+            if let ReloadEvent::Failed(_) = event {
+                failures.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    });
+
+    std::fs::write(&theme, "font_size=20").unwrap();
+    let reload = watcher.check().unwrap().unwrap();
+    assert_eq!(reload.changed, [FontSize::name()]);
+    assert_eq!(watcher.style().get::<FontSize>(), Some(&FontSize(20)));
+    assert_eq!(font_sizes.drain().len(), 1);
+
+    // An edit that keeps the length and modification time is still noticed.
+    let modified = std::fs::metadata(&theme).unwrap().modified().unwrap();
+    std::fs::write(&theme, "font_size=30").unwrap();
+    std::fs::File::options()
+        .write(true)
+        .open(&theme)
+        .unwrap()
+        .set_modified(modified)
+        .unwrap();
+    let reload = watcher.check().unwrap().unwrap();
+    assert_eq!(reload.changed, [FontSize::name()]);
+    assert_eq!(font_sizes.drain().len(), 1);
+
+    // A file that fails to parse keeps the last good style.
+    std::fs::write(&base, "font_size=oops").unwrap();
+    assert!(watcher.check().is_err());
+    assert_eq!(failures.load(Ordering::Relaxed), 1);
+    assert_eq!(watcher.style().get::<FontSize>(), Some(&FontSize(30)));
+    assert!(watcher.check().unwrap().is_none());
+
+    std::fs::write(&base, "font_size=1").unwrap();
+    let reload = watcher.check().unwrap().unwrap();
+    assert_eq!(reload.changed, [NotInheritable::name()]);
+    assert!(watcher.style().get::<NotInheritable>().is_none());
+
+    // Once a file's modification time is old enough to be trusted, its
+    // contents are no longer hashed when the metadata is unchanged.
+    let backdated = std::time::SystemTime::now() - std::time::Duration::from_secs(10);
+    let backdate = |path: &std::path::Path| {
+        std::fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(backdated)
+            .unwrap();
+    };
+    backdate(&theme);
+    assert!(watcher.check().unwrap().unwrap().changed.is_empty());
+    std::fs::write(&theme, "font_size=40").unwrap();
+    backdate(&theme);
+    assert!(watcher.check().unwrap().is_none());
+    std::fs::write(&theme, "font_size=50").unwrap();
+    assert_eq!(
+        watcher.check().unwrap().unwrap().changed,
+        [FontSize::name()]
+    );
+    assert_eq!(watcher.style().get::<FontSize>(), Some(&FontSize(50)));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn dynamic_values() {
+    let tags = Name::new("script", "tags").unwrap();
+    let theme = Name::new("script", "theme").unwrap();
+    let title = Name::new("script", "title").unwrap();
+    let map = |entries: &[(&str, ScriptValue)]| {
+        ScriptValue::Map(
+            entries
+                .iter()
+                .map(|(key, value)| ((*key).to_string(), value.clone()))
+                .collect(),
+        )
+    };
+
+    let parent = Style::new()
+        .with(
+            DynamicValue::new(tags.clone(), vec![ScriptValue::from("a")])
+                .with_inherited(true)
+                .with_merge(MergeStrategy::Concatenate),
+        )
+        .with(
+            DynamicValue::new(
+                theme.clone(),
+                map(&[
+                    ("accent", "red".into()),
+                    ("spacing", map(&[("x", 1_i64.into()), ("y", 2_i64.into())])),
+                ]),
+            )
+            .with_inherited(true)
+            .with_merge(MergeStrategy::DeepMerge),
+        )
+        .with(DynamicValue::new(title.clone(), "parent"));
+    let child = Style::new()
+        .with(
+            DynamicValue::new(tags.clone(), vec![ScriptValue::from("b")])
+                .with_inherited(true)
+                .with_merge(MergeStrategy::Concatenate),
+        )
+        .with(
+            DynamicValue::new(
+                theme.clone(),
+                map(&[("spacing", map(&[("x", 3_i64.into())]))]),
+            )
+            .with_inherited(true)
+            .with_merge(MergeStrategy::DeepMerge),
+        )
+        .with(DynamicValue::new(title.clone(), true));
+
+    let value = |style: &Style, name: &Name| {
+        style
+            .get_by_name(name)
+            .and_then(|component| component.get::<DynamicValue>())
+            .map(|component| component.value.clone())
+    };
+    let inherited = child.clone().inherited_from(&parent);
+    assert_eq!(
+        value(&inherited, &tags),
+        Some(ScriptValue::List(vec!["b".into(), "a".into()]))
+    );
+    assert_eq!(
+        value(&inherited, &theme),
+        Some(map(&[
+            ("accent", "red".into()),
+            ("spacing", map(&[("x", 3_i64.into()), ("y", 2_i64.into())])),
+        ]))
+    );
+    assert_eq!(value(&inherited, &title), Some(true.into()));
+    let merged = Style::new()
+        .with(DynamicValue::new(title.clone(), ScriptValue::Null))
+        .merged_with(&parent);
+    assert!(value(&merged, &title).unwrap().is_null());
+    assert!(StylePatch::diff(&child, &child.clone()).is_empty());
+}
+
+#[test]
+fn conversions() {
+    let registry = ConversionRegistry::new().with(
+        |value| match value {
+            ScriptValue::Int(size) => u32::try_from(*size)
+                .map(FontSize)
+                .map_err(|_| InvalidValue::new("font size out of range")),
+            _ => Err(InvalidValue::new("expected an integer")),
+        },
+        |size: &FontSize| ScriptValue::Int(i64::from(size.0)),
+    );
+    assert!(registry.contains(&FontSize::name()));
+    assert!(!registry.contains(&NotInheritable::name()));
+
+    let custom = Name::new("script", "custom").unwrap();
+    let mut style = Style::new()
+        .with(DynamicValue::new(FontSize::name(), 12_i64))
+        .with(DynamicValue::new(custom.clone(), "value"));
+    style.upgrade(&registry).unwrap();
+    assert_eq!(style.get::<FontSize>(), Some(&FontSize(12)));
+    assert_eq!(style.len(), 2);
+    assert!(style
+        .get_by_name(&custom)
+        .and_then(AnyComponent::get::<DynamicValue>)
+        .is_some());
+
+    style.downgrade(&registry);
+    assert!(style.get::<FontSize>().is_none());
+    let downgraded = style
+        .get_by_name(&FontSize::name())
+        .and_then(AnyComponent::get::<DynamicValue>)
+        .unwrap();
+    assert_eq!(downgraded.value, ScriptValue::Int(12));
+    assert!(downgraded.inherited);
+
+    // Merging a typed component with a value of the same name that has not
+    // been upgraded keeps the existing value.
+    let upgraded = Style::new().with(FontSize(14));
+    let merged = upgraded.clone().merged_with(&style);
+    assert_eq!(merged.get::<FontSize>(), Some(&FontSize(14)));
+    let inherited = upgraded.clone().inherited_from(&style);
+    assert_eq!(inherited.get::<FontSize>(), Some(&FontSize(14)));
+    let merged = style.clone().merged_with(&upgraded);
+    assert!(merged.get::<FontSize>().is_none());
+    assert_eq!(
+        merged
+            .get_by_name(&FontSize::name())
+            .and_then(AnyComponent::get::<DynamicValue>)
+            .map(|value| &value.value),
+        Some(&ScriptValue::Int(12))
+    );
+    let inherited = style.clone().inherited_from(&upgraded);
+    assert!(inherited.get::<FontSize>().is_none());
+
+    let mut invalid = Style::new().with(DynamicValue::new(FontSize::name(), -1_i64));
+    let violations = invalid.upgrade(&registry).unwrap_err();
+    assert_eq!(
+        violations[0].to_string(),
+        "font_size: font size out of range"
+    );
+    assert!(invalid.get_by_name(&FontSize::name()).is_some());
+    assert!(invalid.get::<FontSize>().is_none());
+}
+
+#[test]
+fn authorities() {
+    let renderer = Name::new("renderer", "quality").unwrap();
+    let style = Style::new()
+        .with(ButtonPadding(1))
+        .with(ButtonMargin(2))
+        .with(FontSize(3))
+        .with(DynamicValue::new(renderer.clone(), "high"))
+        .with_keyword::<Opacity>(Keyword::Initial);
+    let gooey = Identifier::new("gooey").unwrap();
+    assert_eq!(style.authority(&gooey).count(), 2);
+    assert_eq!(style.authority(&Identifier::private()).count(), 1);
+
+    let scoped = style.scoped(gooey.clone());
+    assert_eq!(scoped.authority(), &gooey);
+    assert_eq!(scoped.len(), 2);
+    assert_eq!(scoped.get::<ButtonPadding>(), Some(&ButtonPadding(1)));
+    assert!(scoped.get::<FontSize>().is_none());
+    assert!(scoped.get_by_name(&FontSize::name()).is_none());
+    assert!(scoped.keyword(&Opacity::name()).is_none());
+    assert!(style
+        .scoped(Identifier::private())
+        .keyword(&Opacity::name())
+        .is_some());
+    let copied = scoped.to_style();
+    assert_eq!(copied.len(), 2);
+    assert_eq!(copied.get::<ButtonMargin>(), Some(&ButtonMargin(2)));
+    let private = style.scoped(Identifier::private()).to_style();
+    assert_eq!(private.keyword(&Opacity::name()), Some(Keyword::Initial));
+
+    let split = style.split_by_authority();
+    assert_eq!(split.len(), 3);
+    assert_eq!(split.get(&gooey).unwrap().len(), 2);
+    let private = split.get(&Identifier::private()).unwrap();
+    assert_eq!(private.get::<FontSize>(), Some(&FontSize(3)));
+    assert_eq!(private.keyword(&Opacity::name()), Some(Keyword::Initial));
+    let renderer_style = split.get(&renderer.authority).unwrap();
+    assert!(renderer_style.get_by_name(&renderer).is_some());
+    assert!(!style
+        .scoped(Identifier::private())
+        .contains::<ButtonPadding>());
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct RenamedComponent;
+
+impl StyleComponent for RenamedComponent {
+    fn authority() -> Identifier {
+        Identifier::new("renamed").unwrap()
+    }
+}
+
+#[test]
+fn aliased_type_ids() {
+    let canonical = Name::new("renamed", "current").unwrap();
+    assert!(Name::register_alias(
+        RenamedComponent::name(),
+        canonical.clone()
+    ));
+    assert_eq!(
+        ComponentId::of::<RenamedComponent>(),
+        ComponentId::for_name(&canonical)
+    );
+    let style = Style::new().with(RenamedComponent);
+    assert!(style.get_by_name(&canonical).is_some());
+    assert!(Name::unregister_alias(&RenamedComponent::name()));
+}
+
+#[test]
+fn aliases() {
+    let old = Name::new("legacy", "padding").unwrap();
+    let older = Name::new("legacy", "button_padding").unwrap();
+    let style = Style::new().with(DynamicValue::new(old.clone(), 5_i64));
+
+    // Aliases and callbacks are process-wide, so only the aliases registered
+    // by this test are counted.
+    let deprecations = Arc::new(AtomicUsize::new(0));
+    let callback = Name::on_deprecated_alias({
+        let deprecations = deprecations.clone();
+        move |old, new| {
+            if old.authority == "legacy" && new == &ButtonPadding::name() {
+                deprecations.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    });
+    assert!(Name::register_alias(old.clone(), ButtonPadding::name()));
+    assert!(!Name::register_alias(ButtonPadding::name(), old.clone()));
+    assert!(Name::register_alias(older.clone(), old.clone()));
+    assert!(old.is_alias());
+    assert!(!ButtonPadding::name().is_alias());
+
+    assert_eq!(
+        "legacy::padding".parse::<Name>().unwrap(),
+        ButtonPadding::name()
+    );
+    assert_eq!(
+        Name::from_kebab_case("legacy:button-padding").unwrap(),
+        ButtonPadding::name()
+    );
+    assert_eq!(deprecations.load(Ordering::Relaxed), 2);
+
+    // Values added before the alias was registered are still stored under the
+    // deprecated name until the style is canonicalized. Lookups do not report
+    // deprecations.
+    assert!(style.get_by_name(&old).is_none());
+    assert_eq!(deprecations.load(Ordering::Relaxed), 2);
+    let mut canonical = style.clone();
+    canonical.canonicalize();
+    assert_eq!(deprecations.load(Ordering::Relaxed), 3);
+    let value = canonical
+        .get_by_name(&older)
+        .and_then(AnyComponent::get::<DynamicValue>)
+        .unwrap();
+    assert_eq!(value.name, ButtonPadding::name());
+
+    let registry = ConversionRegistry::new().with(
+        |value| match value {
+            ScriptValue::Int(padding) => u32::try_from(*padding)
+                .map(ButtonPadding)
+                .map_err(|_| InvalidValue::new("padding out of range")),
+            _ => Err(InvalidValue::new("expected an integer")),
+        },
+        |padding: &ButtonPadding| ScriptValue::Int(i64::from(padding.0)),
+    );
+    canonical.upgrade(&registry).unwrap();
+    assert_eq!(canonical.get::<ButtonPadding>(), Some(&ButtonPadding(5)));
+    assert_eq!(canonical.len(), 1);
+
+    // Values added under an alias are renamed to the canonical name.
+    let added = Style::new().with(DynamicValue::new(old.clone(), 1_i64));
+    let value = added
+        .get_by_name(&ButtonPadding::name())
+        .and_then(AnyComponent::get::<DynamicValue>)
+        .unwrap();
+    assert_eq!(value.name, ButtonPadding::name());
+    let split = added.split_by_authority();
+    assert!(split.get(&ButtonPadding::name().authority).is_some());
+    assert!(added.authority(&old.authority).next().is_none());
+
+    let observable = ObservableStyle::default();
+    let paddings = observable.subscribe::<ButtonPadding>();
+    let legacy_paddings = observable.subscribe_by_name(old.clone());
+    observable.push(DynamicValue::new(old.clone(), 2_i64));
+    assert_eq!(paddings.drain().len(), 1);
+    assert_eq!(legacy_paddings.drain().len(), 1);
+    assert_eq!(deprecations.load(Ordering::Relaxed), 3);
+
+    // Removing an alias leaves aliases of it in place.
+    assert!(Name::unregister_alias(&old));
+    assert!(!Name::unregister_alias(&old));
+    assert!(!old.is_alias());
+    assert_eq!(older.canonical(), old);
+    assert!(Name::unregister_alias(&older));
+    assert_eq!("legacy::padding".parse::<Name>().unwrap(), old);
+
+    assert!(Name::remove_deprecated_alias_callback(callback));
+    assert!(!Name::remove_deprecated_alias_callback(callback));
+    let mut renamed = Style::new().with(DynamicValue::new(older.clone(), 1_i64));
+    assert!(Name::register_alias(older.clone(), old.clone()));
+    renamed.canonicalize();
+    assert!(Name::unregister_alias(&older));
+    assert!(renamed.get_by_name(&old).is_some());
+    assert_eq!(deprecations.load(Ordering::Relaxed), 3);
+}
@@ -0,0 +1,165 @@
+use crate::Style;
+
+/// A hierarchy of [`Style`]s, where each node inherits from its parent.
+///
+/// [`StyleTree::resolve`] computes the inherited style of every node. When the
+/// `rayon` feature is enabled, [`StyleTree::par_resolve`] produces identical
+/// results by resolving every node at the same depth in parallel.
+#[derive(Debug, Clone, Default)]
+pub struct StyleTree {
+    nodes: Vec<Node>,
+    /// The nodes at each depth of the tree, in the order they were added.
+    levels: Vec<Vec<NodeId>>,
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    parent: Option<NodeId>,
+    depth: usize,
+    children: Vec<NodeId>,
+    style: Style,
+}
+
+/// A node in a [`StyleTree`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    /// Returns the index of this node within the results of
+    /// [`StyleTree::resolve`].
+    #[must_use]
+    pub const fn index(self) -> usize {
+        self.0
+    }
+}
+
+impl StyleTree {
+    /// Returns an empty tree.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            levels: Vec::new(),
+        }
+    }
+
+    /// Adds a node without a parent and returns its id.
+    pub fn push_root(&mut self, style: Style) -> NodeId {
+        self.push_node(None, 0, style)
+    }
+
+    /// Adds a node that inherits from `parent` and returns its id.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `parent` is not a node in this tree.
+    pub fn push_child(&mut self, parent: NodeId, style: Style) -> NodeId {
+        let depth = self.nodes[parent.0].depth + 1;
+        let child = self.push_node(Some(parent), depth, style);
+        self.nodes[parent.0].children.push(child);
+        child
+    }
+
+    fn push_node(&mut self, parent: Option<NodeId>, depth: usize, style: Style) -> NodeId {
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(Node {
+            parent,
+            depth,
+            children: Vec::new(),
+            style,
+        });
+        if self.levels.len() == depth {
+            self.levels.push(Vec::new());
+        }
+        self.levels[depth].push(id);
+        id
+    }
+
+    /// Returns the style assigned to `node`.
+    #[must_use]
+    pub fn style(&self, node: NodeId) -> Option<&Style> {
+        self.nodes.get(node.0).map(|node| &node.style)
+    }
+
+    /// Returns the style assigned to `node`.
+    #[must_use]
+    pub fn style_mut(&mut self, node: NodeId) -> Option<&mut Style> {
+        self.nodes.get_mut(node.0).map(|node| &mut node.style)
+    }
+
+    /// Returns the parent of `node`, if it has one.
+    #[must_use]
+    pub fn parent(&self, node: NodeId) -> Option<NodeId> {
+        self.nodes.get(node.0)?.parent
+    }
+
+    /// Returns the children of `node`.
+    #[must_use]
+    pub fn children(&self, node: NodeId) -> &[NodeId] {
+        self.nodes
+            .get(node.0)
+            .map_or(&[], |node| node.children.as_slice())
+    }
+
+    /// Returns the number of nodes in this tree.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns true if this tree has no nodes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the inherited style of every node, indexed by
+    /// [`NodeId::index`].
+    ///
+    /// Each node's style is resolved using [`Style::inherited_from`] its
+    /// parent's resolved style. Root nodes are resolved against an empty
+    /// style so that their [`Keyword`](crate::Keyword)s are resolved
+    /// consistently.
+    #[must_use]
+    pub fn resolve(&self) -> Vec<Style> {
+        // Parents are always added before their children, so resolving in
+        // insertion order guarantees each parent is resolved first.
+        let mut resolved: Vec<Style> = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let style = node.resolve(&resolved);
+            resolved.push(style);
+        }
+        resolved
+    }
+
+    /// Returns the same result as [`StyleTree::resolve`], resolving all nodes
+    /// at the same depth in parallel.
+    #[cfg(feature = "rayon")]
+    #[must_use]
+    pub fn par_resolve(&self) -> Vec<Style> {
+        use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+
+        let mut resolved = vec![Style::new(); self.nodes.len()];
+        for level in &self.levels {
+            let styles = level
+                .par_iter()
+                .map(|id| self.nodes[id.0].resolve(&resolved))
+                .collect::<Vec<_>>();
+            for (id, style) in level.iter().zip(styles) {
+                resolved[id.0] = style;
+            }
+        }
+        resolved
+    }
+}
+
+impl Node {
+    /// Resolves this node against its parent's entry in `resolved`, which
+    /// must already be resolved.
+    fn resolve(&self, resolved: &[Style]) -> Style {
+        match self.parent {
+            Some(parent) => self.style.clone().inherited_from(&resolved[parent.0]),
+            None => self.style.clone().inherited_from(&Style::new()),
+        }
+    }
+}
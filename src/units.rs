@@ -0,0 +1,265 @@
+use std::fmt::{Debug, Display};
+use std::sync::Arc;
+
+use crate::{ComponentId, Style, StyleComponent};
+
+/// A measurement with a [`Unit`].
+///
+/// Relative measurements can be converted to absolute measurements using a
+/// [`ResolutionContext`].
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct Dimension {
+    /// The measurement's value.
+    pub value: f32,
+    /// The unit of the value.
+    pub unit: Unit,
+}
+
+impl Dimension {
+    /// Returns a new dimension.
+    #[must_use]
+    pub const fn new(value: f32, unit: Unit) -> Self {
+        Self { value, unit }
+    }
+
+    /// Returns a dimension measured in logical pixels.
+    #[must_use]
+    pub const fn px(value: f32) -> Self {
+        Self::new(value, Unit::Px)
+    }
+
+    /// Returns a dimension measured in points.
+    #[must_use]
+    pub const fn pt(value: f32) -> Self {
+        Self::new(value, Unit::Pt)
+    }
+
+    /// Returns a dimension relative to the current font size.
+    #[must_use]
+    pub const fn em(value: f32) -> Self {
+        Self::new(value, Unit::Em)
+    }
+
+    /// Returns a dimension relative to the root font size.
+    #[must_use]
+    pub const fn rem(value: f32) -> Self {
+        Self::new(value, Unit::Rem)
+    }
+
+    /// Returns a dimension relative to the container's size.
+    #[must_use]
+    pub const fn percent(value: f32) -> Self {
+        Self::new(value, Unit::Percent)
+    }
+
+    /// Returns true if this dimension's unit depends on a
+    /// [`ResolutionContext`].
+    #[must_use]
+    pub const fn is_relative(&self) -> bool {
+        self.unit.is_relative()
+    }
+
+    /// Returns this dimension converted to logical pixels.
+    ///
+    /// [`Unit::Percent`] values are resolved against the container's size
+    /// along `axis`.
+    #[must_use]
+    pub fn to_px(self, context: &ResolutionContext, axis: Axis) -> f32 {
+        match self.unit {
+            Unit::Px => self.value,
+            Unit::Pt => self.value * PX_PER_PT,
+            Unit::Em => self.value * context.font_size,
+            Unit::Rem => self.value * context.root_font_size,
+            Unit::Percent => {
+                let basis = match axis {
+                    Axis::Horizontal => context.container_width,
+                    Axis::Vertical => context.container_height,
+                };
+                self.value / 100. * basis
+            }
+        }
+    }
+
+    /// Returns this dimension converted to physical pixels using the
+    /// context's [`scale`](ResolutionContext::scale).
+    #[must_use]
+    pub fn to_physical_px(self, context: &ResolutionContext, axis: Axis) -> f32 {
+        self.to_px(context, axis) * context.scale
+    }
+
+    /// Returns this dimension converted to an absolute dimension measured in
+    /// logical pixels.
+    #[must_use]
+    pub fn to_absolute(self, context: &ResolutionContext, axis: Axis) -> Self {
+        Self::px(self.to_px(context, axis))
+    }
+}
+
+impl Display for Dimension {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.value, self.unit)
+    }
+}
+
+/// The number of logical pixels in a point. Logical pixels are defined as
+/// 1/96th of an inch, while points are 1/72nd of an inch.
+const PX_PER_PT: f32 = 96. / 72.;
+
+/// A unit of measurement for a [`Dimension`].
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Unit {
+    /// Logical pixels, 1/96th of an inch.
+    #[default]
+    Px,
+    /// Points, 1/72nd of an inch.
+    Pt,
+    /// A multiple of the current font size.
+    Em,
+    /// A multiple of the root font size.
+    Rem,
+    /// A percentage of the container's size.
+    Percent,
+}
+
+impl Unit {
+    /// Returns true if this unit depends on a [`ResolutionContext`].
+    #[must_use]
+    pub const fn is_relative(self) -> bool {
+        matches!(self, Self::Em | Self::Rem | Self::Percent)
+    }
+}
+
+impl Display for Unit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Unit::Px => "px",
+            Unit::Pt => "pt",
+            Unit::Em => "em",
+            Unit::Rem => "rem",
+            Unit::Percent => "%",
+        })
+    }
+}
+
+/// An axis used to resolve [`Unit::Percent`] measurements.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Axis {
+    /// The horizontal axis, resolved against the container's width.
+    Horizontal,
+    /// The vertical axis, resolved against the container's height.
+    Vertical,
+}
+
+/// The information needed to convert relative [`Dimension`]s into absolute
+/// measurements.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResolutionContext {
+    /// The number of physical pixels per logical pixel.
+    pub scale: f32,
+    /// The current font size, in logical pixels. Used to resolve
+    /// [`Unit::Em`].
+    pub font_size: f32,
+    /// The root font size, in logical pixels. Used to resolve
+    /// [`Unit::Rem`].
+    pub root_font_size: f32,
+    /// The width of the container, in logical pixels.
+    pub container_width: f32,
+    /// The height of the container, in logical pixels.
+    pub container_height: f32,
+}
+
+impl Default for ResolutionContext {
+    fn default() -> Self {
+        Self {
+            scale: 1.,
+            font_size: 16.,
+            root_font_size: 16.,
+            container_width: 0.,
+            container_height: 0.,
+        }
+    }
+}
+
+/// The component used as the basis for resolving [`Unit::Em`] by
+/// [`Style::resolved_inherited_from`].
+#[derive(Clone)]
+pub struct FontSizeSource {
+    component: ComponentId,
+    font_size: Arc<FontSizeFn>,
+}
+
+type FontSizeFn = dyn Fn(&Style) -> Option<Dimension> + Send + Sync;
+
+impl FontSizeSource {
+    /// Returns a source that reads the font size from the component of type
+    /// `T` using `font_size`.
+    pub fn new<T: StyleComponent>(
+        font_size: impl Fn(&T) -> Dimension + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            component: T::component_id(),
+            font_size: Arc::new(move |style| style.get::<T>().map(&font_size)),
+        }
+    }
+
+    /// Returns the id of the component containing the font size.
+    #[must_use]
+    pub const fn component_id(&self) -> ComponentId {
+        self.component
+    }
+
+    /// Returns the font size contained in `style`, if present.
+    #[must_use]
+    pub fn font_size(&self, style: &Style) -> Option<Dimension> {
+        (self.font_size)(style)
+    }
+}
+
+impl Debug for FontSizeSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("FontSizeSource")
+            .field(&self.component.name())
+            .finish()
+    }
+}
+
+#[test]
+fn resolution() {
+    let context = ResolutionContext {
+        scale: 2.,
+        font_size: 10.,
+        root_font_size: 20.,
+        container_width: 200.,
+        container_height: 100.,
+    };
+    let absolute = |dimension: Dimension, axis| dimension.to_absolute(&context, axis);
+    assert_eq!(
+        absolute(Dimension::px(3.), Axis::Horizontal),
+        Dimension::px(3.)
+    );
+    assert_eq!(
+        absolute(Dimension::pt(3.), Axis::Horizontal),
+        Dimension::px(4.)
+    );
+    assert_eq!(
+        absolute(Dimension::em(1.5), Axis::Horizontal),
+        Dimension::px(15.)
+    );
+    assert_eq!(
+        absolute(Dimension::rem(1.5), Axis::Horizontal),
+        Dimension::px(30.)
+    );
+    assert_eq!(
+        absolute(Dimension::percent(10.), Axis::Horizontal),
+        Dimension::px(20.)
+    );
+    assert_eq!(
+        absolute(Dimension::percent(10.), Axis::Vertical),
+        Dimension::px(10.)
+    );
+    assert_eq!(
+        Dimension::px(Dimension::em(1.).to_physical_px(&context, Axis::Vertical)),
+        Dimension::px(20.)
+    );
+    assert_eq!(Dimension::rem(2.).to_string(), "2rem");
+}
@@ -0,0 +1,48 @@
+use std::borrow::Cow;
+use std::fmt::Display;
+
+use crate::Name;
+
+/// An error describing why a component's value is invalid.
+///
+/// Returned by [`StyleComponent::validate`](crate::StyleComponent::validate).
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct InvalidValue(Cow<'static, str>);
+
+impl InvalidValue {
+    /// Returns a new error with `message` describing why the value is invalid.
+    pub fn new(message: impl Into<Cow<'static, str>>) -> Self {
+        Self(message.into())
+    }
+
+    /// Returns the message describing why the value is invalid.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for InvalidValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for InvalidValue {}
+
+/// An invalid component value found in a [`Style`](crate::Style).
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Violation {
+    /// The name of the component with the invalid value.
+    pub name: Name,
+    /// The reason the value is invalid.
+    pub error: InvalidValue,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.name, self.error)
+    }
+}
+
+impl std::error::Error for Violation {}
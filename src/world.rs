@@ -0,0 +1,531 @@
+use std::any::Any;
+use std::fmt::{Debug, Display};
+
+use alot::{LotId, Lots};
+use kempt::Map;
+
+use crate::any::AnyComponent;
+use crate::components::DynamicComponent;
+use crate::query::{Query, QueryIter, QueryMut, ReadOnlyQuery};
+use crate::{ComponentId, ComponentSet, Name, Style, StyleComponent};
+
+/// A collection of entities whose components are stored contiguously by
+/// component type.
+///
+/// Each component type is stored in a [`Column`] keyed by the component's
+/// [`ComponentId`], which is derived from its [`Name`]. This allows iterating
+/// every value of a component type without visiting unrelated components,
+/// while [`StyleWorld::style`] and [`StyleWorld::spawn_style`] convert between
+/// an entity and a standalone [`Style`].
+#[derive(Default)]
+pub struct StyleWorld {
+    entities: Lots<EntityData>,
+    /// All live entities. Each entity's index is stored in
+    /// [`EntityData::position`].
+    order: Vec<Entity>,
+    columns: Map<ComponentId, Box<dyn AnyColumn>>,
+}
+
+struct EntityData {
+    position: usize,
+    /// The index of this entity's value within each column it has a value in.
+    slots: Map<ComponentId, usize>,
+    components: ComponentSet,
+}
+
+/// An entity in a [`StyleWorld`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Entity(LotId);
+
+impl Debug for StyleWorld {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries(
+                self.order
+                    .iter()
+                    .filter_map(|entity| Some((entity, self.style(*entity)?))),
+            )
+            .finish()
+    }
+}
+
+impl StyleWorld {
+    /// Returns an empty world.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new entity with no components.
+    pub fn spawn(&mut self) -> Entity {
+        let entity = Entity(self.entities.push(EntityData {
+            position: self.order.len(),
+            slots: Map::new(),
+            components: ComponentSet::new(),
+        }));
+        self.order.push(entity);
+        entity
+    }
+
+    /// Creates a new entity containing a copy of each component in `style`.
+    ///
+    /// [`Keyword`](crate::Keyword)s contained in `style` are not copied.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ColumnTypeMismatch`] without spawning an entity if a
+    /// component in `style` has the same [`ComponentId`] as a column storing
+    /// a different type.
+    pub fn spawn_style(&mut self, style: &Style) -> Result<Entity, ColumnTypeMismatch> {
+        self.check_columns(style)?;
+        let entity = self.spawn();
+        self.insert_style(entity, style)?;
+        Ok(entity)
+    }
+
+    /// Removes `entity` and all of its components. Returns false if the entity
+    /// was not found.
+    pub fn despawn(&mut self, entity: Entity) -> bool {
+        let Some(data) = self.entities.remove(entity.0) else {
+            return false;
+        };
+        self.order.swap_remove(data.position);
+        if let Some(moved) = self.order.get(data.position).copied() {
+            if let Some(moved) = self.entities.get_mut(moved.0) {
+                moved.position = data.position;
+            }
+        }
+        for field in data.slots {
+            let (id, index) = field.into_parts();
+            if let Some(column) = self.columns.get_mut(&id) {
+                let moved = column.swap_remove(index);
+                self.relocate(moved, id, index);
+            }
+        }
+        true
+    }
+
+    /// Returns true if `entity` exists in this world.
+    #[must_use]
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.entities.get(entity.0).is_some()
+    }
+
+    /// Returns all entities in this world.
+    #[must_use]
+    pub fn entities(&self) -> &[Entity] {
+        &self.order
+    }
+
+    /// Returns the number of entities in this world.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Returns true if this world contains no entities.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// Adds `component` to `entity`, replacing any existing value of the same
+    /// type. Returns `Ok(false)` if the entity was not found.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ColumnTypeMismatch`] if `component` has the same
+    /// [`ComponentId`] as a column storing a different type, such as when a
+    /// [`DynamicValue`](crate::DynamicValue) shares the name of a typed
+    /// component.
+    pub fn insert<T: DynamicComponent + Clone>(
+        &mut self,
+        entity: Entity,
+        component: T,
+    ) -> Result<bool, ColumnTypeMismatch> {
+        let id = component.component_id();
+        let Some(data) = self.entities.get_mut(entity.0) else {
+            return Ok(false);
+        };
+        let Some(column) = self
+            .columns
+            .entry(id)
+            .or_insert_with(|| Box::<Column<T>>::default())
+            .as_any_mut()
+            .downcast_mut::<Column<T>>()
+        else {
+            return Err(ColumnTypeMismatch {
+                name: component.name(),
+            });
+        };
+        if let Some(index) = data.slots.get(&id) {
+            column.values[*index] = component;
+        } else {
+            data.slots.insert(id, column.values.len());
+            data.components.insert(id);
+            column.entities.push(entity);
+            column.values.push(component);
+        }
+        Ok(true)
+    }
+
+    /// Adds a copy of each component in `style` to `entity`, replacing any
+    /// existing values of the same types. Returns `Ok(false)` if the entity
+    /// was not found.
+    ///
+    /// [`Keyword`](crate::Keyword)s contained in `style` are not copied.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ColumnTypeMismatch`] without modifying `entity` if a
+    /// component in `style` has the same [`ComponentId`] as a column storing
+    /// a different type.
+    pub fn insert_style(
+        &mut self,
+        entity: Entity,
+        style: &Style,
+    ) -> Result<bool, ColumnTypeMismatch> {
+        self.check_columns(style)?;
+        let Some(data) = self.entities.get_mut(entity.0) else {
+            return Ok(false);
+        };
+        for component in style {
+            let id = component.component_id();
+            let column = self
+                .columns
+                .entry(id)
+                .or_insert_with(|| component.new_column());
+            if let Some(index) = data.slots.get(&id) {
+                column.replace(*index, component.clone());
+            } else {
+                data.components.insert(id);
+                data.slots
+                    .insert(id, column.push(entity, component.clone()));
+            }
+        }
+        Ok(true)
+    }
+
+    /// Returns an error if any component in `style` cannot be stored in the
+    /// existing column for its id.
+    fn check_columns(&self, style: &Style) -> Result<(), ColumnTypeMismatch> {
+        for component in style {
+            if let Some(column) = self.columns.get(&component.component_id()) {
+                if !column.accepts(component) {
+                    return Err(ColumnTypeMismatch {
+                        name: component.name(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the component of type `T` for `entity`, if present.
+    #[must_use]
+    pub fn get<T: StyleComponent + Clone>(&self, entity: Entity) -> Option<&T> {
+        let id = T::component_id();
+        let index = *self.entities.get(entity.0)?.slots.get(&id)?;
+        self.column::<T>()?.values.get(index)
+    }
+
+    /// Returns a mutable reference to the component of type `T` for `entity`,
+    /// if present.
+    #[must_use]
+    pub fn get_mut<T: StyleComponent + Clone>(&mut self, entity: Entity) -> Option<&mut T> {
+        let id = T::component_id();
+        let index = *self.entities.get(entity.0)?.slots.get(&id)?;
+        self.column_mut::<T>()?.values.get_mut(index)
+    }
+
+    /// Returns a copy of the component named `name` for `entity`, if present.
+    #[must_use]
+    pub fn get_by_name(&self, entity: Entity, name: &Name) -> Option<AnyComponent> {
+        let id = ComponentId::find(name)?;
+        let index = *self.entities.get(entity.0)?.slots.get(&id)?;
+        Some(self.columns.get(&id)?.get(index))
+    }
+
+    /// Removes and returns the component of type `T` from `entity`, if
+    /// present.
+    pub fn remove<T: StyleComponent + Clone>(&mut self, entity: Entity) -> Option<T> {
+        self.remove_by_id(entity, T::component_id())?
+            .into_inner()
+            .ok()
+    }
+
+    /// Removes and returns the component named `name` from `entity`, if
+    /// present.
+    pub fn remove_by_name(&mut self, entity: Entity, name: &Name) -> Option<AnyComponent> {
+        self.remove_by_id(entity, ComponentId::find(name)?)
+    }
+
+    fn remove_by_id(&mut self, entity: Entity, id: ComponentId) -> Option<AnyComponent> {
+        let data = self.entities.get_mut(entity.0)?;
+        let index = data.slots.remove(&id)?.value;
+        data.components.remove(id);
+        let column = self.columns.get_mut(&id)?;
+        let removed = column.get(index);
+        let moved = column.swap_remove(index);
+        self.relocate(moved, id, index);
+        Some(removed)
+    }
+
+    /// Updates the slot of the entity whose value for `id` was moved to
+    /// `index` by [`AnyColumn::swap_remove`].
+    fn relocate(&mut self, moved: Option<Entity>, id: ComponentId, index: usize) {
+        if let Some(slot) = moved
+            .and_then(|moved| self.entities.get_mut(moved.0))
+            .and_then(|data| data.slots.get_mut(&id))
+        {
+            *slot = index;
+        }
+    }
+
+    /// Returns a [`Style`] containing a copy of each component of `entity`,
+    /// or `None` if the entity was not found.
+    #[must_use]
+    pub fn style(&self, entity: Entity) -> Option<Style> {
+        let data = self.entities.get(entity.0)?;
+        let mut style = Style::with_capacity(data.slots.len());
+        for field in &data.slots {
+            if let Some(column) = self.columns.get(field.key()) {
+                style.push_any(column.get(field.value));
+            }
+        }
+        Some(style)
+    }
+
+    /// Returns an iterator over every entity that has a value for each
+    /// component referenced by `Q`.
+    ///
+    /// `Q` is a component reference or a tuple of references, e.g.
+    /// `(&FontSize, Option<&TextColor>)`. Use [`QueryMut`] via
+    /// [`StyleWorld::query_mut`] to access components mutably.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `Q` references the same component more than
+    /// once.
+    #[must_use]
+    pub fn query<Q: ReadOnlyQuery>(&self) -> QueryIter<'_, Q> {
+        QueryIter::new(self)
+    }
+
+    /// Returns a query over every entity that has a value for each component
+    /// referenced by `Q`, which may include `&mut T` and `Option<&mut T>`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `Q` references the same component more than
+    /// once.
+    #[must_use]
+    pub fn query_mut<Q: Query>(&mut self) -> QueryMut<'_, Q> {
+        QueryMut::new(self)
+    }
+
+    /// Returns the index of `entity`'s value within the column for `id`.
+    pub(crate) fn slot(&self, entity: Entity, id: ComponentId) -> Option<usize> {
+        self.entities.get(entity.0)?.slots.get(&id).copied()
+    }
+
+    /// Returns the set of components `entity` has values for.
+    pub(crate) fn components_of(&self, entity: Entity) -> Option<&ComponentSet> {
+        self.entities.get(entity.0).map(|data| &data.components)
+    }
+
+    /// Temporarily removes the column for `T` so that it can be borrowed
+    /// mutably while the rest of the world is borrowed immutably. The column
+    /// must be returned using [`StyleWorld::restore_column`].
+    pub(crate) fn take_column<T: StyleComponent + Clone>(&mut self) -> Option<Column<T>> {
+        let id = T::component_id();
+        if !self.columns.get(&id)?.as_any().is::<Column<T>>() {
+            return None;
+        }
+        let column = self.columns.remove(&id)?.value;
+        column.into_any().downcast().ok().map(|column| *column)
+    }
+
+    pub(crate) fn restore_column<T: StyleComponent + Clone>(&mut self, column: Column<T>) {
+        self.columns.insert(T::component_id(), Box::new(column));
+    }
+
+    /// Returns the column storing all values of the component `T`, if any
+    /// values have been inserted.
+    #[must_use]
+    pub fn column<T: StyleComponent + Clone>(&self) -> Option<&Column<T>> {
+        self.columns
+            .get(&T::component_id())?
+            .as_any()
+            .downcast_ref()
+    }
+
+    /// Returns the column storing all values of the component `T`, if any
+    /// values have been inserted.
+    #[must_use]
+    pub fn column_mut<T: StyleComponent + Clone>(&mut self) -> Option<&mut Column<T>> {
+        self.columns
+            .get_mut(&T::component_id())?
+            .as_any_mut()
+            .downcast_mut()
+    }
+}
+
+/// The contiguous storage of every value of a component type in a
+/// [`StyleWorld`].
+///
+/// Values are not stored in any particular order. The entity at each index of
+/// [`Column::entities`] owns the value at the same index of
+/// [`Column::values`].
+#[derive(Debug, Clone)]
+pub struct Column<T> {
+    entities: Vec<Entity>,
+    values: Vec<T>,
+}
+
+impl<T> Default for Column<T> {
+    fn default() -> Self {
+        Self {
+            entities: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+}
+
+impl<T> Column<T> {
+    /// Returns the entities that have a value in this column.
+    #[must_use]
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    /// Returns the values in this column.
+    #[must_use]
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Returns the values in this column.
+    #[must_use]
+    pub fn values_mut(&mut self) -> &mut [T] {
+        &mut self.values
+    }
+
+    /// Returns an iterator over each entity and its value.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &T)> + '_ {
+        self.entities.iter().copied().zip(&self.values)
+    }
+
+    /// Returns an iterator over each entity and a mutable reference to its
+    /// value.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Entity, &mut T)> + '_ {
+        self.entities.iter().copied().zip(&mut self.values)
+    }
+
+    /// Returns the number of values in this column.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns true if this column contains no values.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// A type-erased [`Column`].
+pub(crate) trait AnyColumn: Send + Sync + 'static {
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+
+    fn get(&self, index: usize) -> AnyComponent;
+
+    /// Returns true if `component` has the type stored in this column.
+    fn accepts(&self, component: &AnyComponent) -> bool;
+
+    /// Appends `component` for `entity` and returns its index.
+    fn push(&mut self, entity: Entity, component: AnyComponent) -> usize;
+
+    fn replace(&mut self, index: usize, component: AnyComponent);
+
+    /// Removes the value at `index`, returning the entity whose value was moved
+    /// into `index`, if any.
+    fn swap_remove(&mut self, index: usize) -> Option<Entity>;
+}
+
+impl<T> Column<T>
+where
+    T: DynamicComponent + Clone,
+{
+    fn downcast(component: AnyComponent) -> T {
+        component
+            .into_inner()
+            .unwrap_or_else(|component| panic!("column type mismatch for {}", component.name()))
+    }
+}
+
+impl<T> AnyColumn for Column<T>
+where
+    T: DynamicComponent + Clone,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    fn get(&self, index: usize) -> AnyComponent {
+        AnyComponent::new(self.values[index].clone())
+    }
+
+    fn accepts(&self, component: &AnyComponent) -> bool {
+        component.get::<T>().is_some()
+    }
+
+    fn push(&mut self, entity: Entity, component: AnyComponent) -> usize {
+        let index = self.values.len();
+        self.values.push(Self::downcast(component));
+        self.entities.push(entity);
+        index
+    }
+
+    fn replace(&mut self, index: usize, component: AnyComponent) {
+        self.values[index] = Self::downcast(component);
+    }
+
+    fn swap_remove(&mut self, index: usize) -> Option<Entity> {
+        self.values.swap_remove(index);
+        self.entities.swap_remove(index);
+        self.entities.get(index).copied()
+    }
+}
+
+/// An error returned when a component cannot be stored in a [`StyleWorld`]
+/// because another type is already stored under its [`ComponentId`].
+///
+/// This happens when a typed component and a
+/// [`DynamicValue`](crate::DynamicValue) share the same [`Name`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ColumnTypeMismatch {
+    /// The name of the component that could not be stored.
+    pub name: Name,
+}
+
+impl Display for ColumnTypeMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is already stored with a different type", self.name)
+    }
+}
+
+impl std::error::Error for ColumnTypeMismatch {}
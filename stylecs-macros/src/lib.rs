@@ -2,7 +2,7 @@ use attribute_derive::Attribute;
 use manyhow::manyhow;
 use proc_macro2::{Ident, TokenStream};
 use quote::quote;
-use syn::{DeriveInput, Expr};
+use syn::{Data, DeriveInput, Expr, Index, Member};
 
 #[derive(Attribute, Debug)]
 #[attribute(ident = style)]
@@ -11,6 +11,19 @@ struct StyleComponent {
     authority: Option<Ident>,
     inherited: Option<bool>,
     merge: Option<Expr>,
+    initial: Option<Expr>,
+    resolve_units: Option<Expr>,
+    resolve_against_parent: Option<Expr>,
+    normalize: Option<Expr>,
+    validate: Option<Expr>,
+    equivalent: Option<Expr>,
+    partial_eq: bool,
+}
+
+#[derive(Attribute, Debug)]
+#[attribute(ident = style)]
+struct FieldStyle {
+    range: Option<Expr>,
 }
 
 #[manyhow]
@@ -20,6 +33,7 @@ pub fn style_component_derive(input: TokenStream) -> manyhow::Result<TokenStream
         attrs,
         ident,
         generics,
+        data,
         ..
     } = syn::parse2(input)?;
     let StyleComponent {
@@ -27,12 +41,19 @@ pub fn style_component_derive(input: TokenStream) -> manyhow::Result<TokenStream
         authority,
         inherited,
         merge,
+        initial,
+        resolve_units,
+        resolve_against_parent,
+        normalize,
+        validate: validate_expr,
+        equivalent,
+        partial_eq,
     } = StyleComponent::from_attributes(&attrs)?;
 
     let name = if let Some(name) = name {
         validate(&name)?
     } else {
-        stylecs_shared::pascal_case_to_snake_case(ident.to_string()).map_err(|_| manyhow::error_message!(ident.span(), "An invalid character for a stylecs Identifier was found. A name must be manually provided for this type."))?
+        stylecs_shared::pascal_case_to_snake_case(ident.to_string()).map_err(|err| manyhow::error_message!(ident.span(), "{err}. A name must be manually provided for this type."))?
     };
 
     let name = if let Some(authority) = authority {
@@ -49,6 +70,13 @@ pub fn style_component_derive(input: TokenStream) -> manyhow::Result<TokenStream
             }
         )
     });
+    let initial = initial.map(|expr| {
+        quote!(
+            fn initial_value() -> Option<Self> {
+                Some(#expr)
+            }
+        )
+    });
     let merge = merge.map(|expr| {
         quote!(
             fn merge(&mut self, other: &Self) {
@@ -57,14 +85,118 @@ pub fn style_component_derive(input: TokenStream) -> manyhow::Result<TokenStream
         )
     });
 
+    let resolve_units = resolve_units.map(|expr| {
+        quote!(
+            fn resolve_units(&mut self, context: &::stylecs::ResolutionContext) {
+                #expr;
+            }
+        )
+    });
+
+    let resolve_against_parent = resolve_against_parent.map(|expr| {
+        quote!(
+            fn resolve_against_parent(&mut self, parent: &Self) {
+                #expr;
+            }
+        )
+    });
+
+    let normalize = normalize.map(|expr| {
+        quote!(
+            fn normalize(&mut self) {
+                #expr;
+            }
+        )
+    });
+
+    let equivalent = match (equivalent, partial_eq) {
+        (Some(_), true) => {
+            manyhow::bail!(ident, "`equivalent` and `partial_eq` cannot be combined")
+        }
+        (Some(expr), false) => Some(expr),
+        (None, true) => Some(syn::parse_quote!(self == other)),
+        (None, false) => None,
+    }
+    .map(|expr: Expr| {
+        quote!(
+            fn equivalent(&self, other: &Self) -> bool {
+                #expr
+            }
+        )
+    });
+
+    let mut ranges = Vec::new();
+    match &data {
+        Data::Struct(data) => {
+            for (index, field) in data.fields.iter().enumerate() {
+                let FieldStyle { range } = FieldStyle::from_attributes(&field.attrs)?;
+                let Some(range) = range else { continue };
+                let (member, label) = if let Some(field) = &field.ident {
+                    (Member::Named(field.clone()), format!("{ident}.{field}"))
+                } else {
+                    (
+                        Member::Unnamed(Index::from(index)),
+                        format!("{ident}.{index}"),
+                    )
+                };
+                ranges.push(quote!({
+                    let range = #range;
+                    if !range.contains(&self.#member) {
+                        return Err(::stylecs::InvalidValue::new(format!(
+                            "{} must be within {:?}",
+                            #label,
+                            range
+                        )));
+                    }
+                }));
+            }
+        }
+        Data::Enum(data) => {
+            for field in data.variants.iter().flat_map(|variant| &variant.fields) {
+                if FieldStyle::from_attributes(&field.attrs)?.range.is_some() {
+                    manyhow::bail!(field, "`range` is only supported on struct fields");
+                }
+            }
+        }
+        Data::Union(_) => {}
+    }
+    let validate = (!ranges.is_empty() || validate_expr.is_some()).then(|| {
+        let result = validate_expr.map_or_else(|| quote!(Ok(())), |expr| quote!(#expr));
+        quote!(
+            fn validate(&self) -> Result<(), ::stylecs::InvalidValue> {
+                #(#ranges)*
+                #result
+            }
+        )
+    });
+
+    // The id can only be cached in a static when the type is not generic, as
+    // statics are shared between all instantiations of a generic function.
+    let component_id = generics.params.is_empty().then(|| {
+        quote!(
+            fn component_id() -> ::stylecs::ComponentId {
+                static ID: ::std::sync::OnceLock<::stylecs::ComponentId> =
+                    ::std::sync::OnceLock::new();
+                *ID.get_or_init(::stylecs::ComponentId::of::<Self>)
+            }
+        )
+    });
+
     Ok(quote! {
         impl<#generics> stylecs::StyleComponent for #ident<#generics> {
             fn name() -> ::stylecs::Name {
                 static NAME: ::stylecs::StaticName = #name;
                 NAME.to_name()
             }
+            #component_id
             #inherited
+            #initial
             #merge
+            #resolve_units
+            #resolve_against_parent
+            #normalize
+            #validate
+            #equivalent
         }
     })
 }
@@ -72,7 +204,12 @@ pub fn style_component_derive(input: TokenStream) -> manyhow::Result<TokenStream
 fn validate(name: &Ident) -> manyhow::Result<String> {
     let location = name.span();
     let name = name.to_string();
-    stylecs_shared::validate_identifier(&name)
-        .map_err(|_| manyhow::error_message!(location, "invalid character in identifier"))?;
+    stylecs_shared::validate_identifier_kind(&name).map_err(|kind| {
+        manyhow::error_message!(
+            location,
+            "{}",
+            stylecs_shared::InvalidIdentifier::new(name.clone(), kind)
+        )
+    })?;
     Ok(name)
 }
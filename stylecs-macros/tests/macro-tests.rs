@@ -1,26 +1,95 @@
-use stylecs::{Identifier, StyleComponent};
+use stylecs::{
+    Axis, ComponentId, Dimension, Identifier, InvalidValue, ResolutionContext, StyleComponent,
+};
 
-#[derive(StyleComponent, Debug, Clone)]
-#[style(inherited = true)]
+#[derive(StyleComponent, Debug, Eq, PartialEq, Clone)]
+#[style(inherited = true, initial = Inheritable)]
 struct Inheritable;
 
 #[derive(StyleComponent, Debug, Clone)]
 struct NotInheritable;
 
 #[derive(StyleComponent, Debug, Eq, PartialEq, Clone)]
-#[style(name = additive, authority = gooey, inherited = false, merge = self.0 += other.0)]
+#[style(name = additive, authority = gooey, inherited = false, merge = self.0 += other.0, equivalent = self == other)]
 struct AdditiveMerge(u32);
 
+#[derive(StyleComponent, Debug, PartialEq, Clone)]
+#[style(resolve_units = self.0 = self.0.to_absolute(context, Axis::Horizontal))]
+struct Width(Dimension);
+
+#[derive(StyleComponent, Debug, PartialEq, Clone)]
+#[style(resolve_against_parent = self.0 *= parent.0, partial_eq)]
+struct Scale(u32);
+
+#[derive(StyleComponent, Debug, PartialEq, Clone)]
+#[style(normalize = self.0 = self.0.clamp(0.0, 1.0))]
+struct Opacity(#[style(range = 0.0..=1.0)] f32);
+
+#[derive(StyleComponent, Debug, PartialEq, Clone)]
+#[style(validate = (self.min <= self.max)
+    .then_some(())
+    .ok_or_else(|| InvalidValue::new("min exceeds max")))]
+struct Bounds {
+    #[style(range = 0..)]
+    min: i32,
+    max: i32,
+}
+
+#[test]
+fn validation() {
+    assert!(Opacity(0.5).validate().is_ok());
+    assert_eq!(
+        Opacity(1.5).validate(),
+        Err(InvalidValue::new("Opacity.0 must be within 0.0..=1.0"))
+    );
+    let mut opacity = Opacity(1.5);
+    opacity.normalize();
+    assert_eq!(opacity, Opacity(1.0));
+
+    assert!(Bounds { min: 0, max: 1 }.validate().is_ok());
+    assert_eq!(
+        Bounds { min: -1, max: 1 }.validate(),
+        Err(InvalidValue::new("Bounds.min must be within 0.."))
+    );
+    assert_eq!(
+        Bounds { min: 2, max: 1 }.validate(),
+        Err(InvalidValue::new("min exceeds max"))
+    );
+}
+
 #[test]
 fn defined_correctly() {
     assert_eq!(Inheritable::name().name, "inheritable");
     assert_eq!(Inheritable::name().authority, Identifier::private());
     assert!(Inheritable::inherited());
     assert!(!NotInheritable::inherited());
+    assert_eq!(Inheritable::initial_value(), Some(Inheritable));
+    assert!(NotInheritable::initial_value().is_none());
     assert_eq!(NotInheritable::name().name, "not_inheritable");
+    assert_eq!(
+        Inheritable::component_id(),
+        ComponentId::for_name(&Inheritable::name())
+    );
+    assert_ne!(Inheritable::component_id(), NotInheritable::component_id());
+    assert_eq!(Inheritable::component_id().name(), Inheritable::name());
     let mut mergable = AdditiveMerge(1);
     mergable.merge(&AdditiveMerge(2));
     assert_eq!(mergable, AdditiveMerge(3));
     assert_eq!(AdditiveMerge::name().name, "additive");
     assert_eq!(AdditiveMerge::name().authority, "gooey");
+    assert!(AdditiveMerge(1).equivalent(&AdditiveMerge(1)));
+    assert!(!AdditiveMerge(1).equivalent(&AdditiveMerge(2)));
+    assert!(Scale(1).equivalent(&Scale(1)));
+    assert!(!Scale(1).equivalent(&Scale(2)));
+    // Without `equivalent` or `partial_eq`, values are never equivalent.
+    assert!(!NotInheritable.equivalent(&NotInheritable));
+    let mut width = Width(Dimension::em(2.));
+    width.resolve_units(&ResolutionContext {
+        font_size: 10.,
+        ..ResolutionContext::default()
+    });
+    assert_eq!(width, Width(Dimension::px(20.)));
+    let mut scale = Scale(2);
+    scale.resolve_against_parent(&Scale(3));
+    assert_eq!(scale, Scale(6));
 }
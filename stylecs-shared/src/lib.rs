@@ -1,61 +1,286 @@
 use std::error::Error;
 use std::fmt::Display;
 
+/// Validates that `name` is a non-empty identifier containing only `a-z`,
+/// `A-Z`, `0-9`, or `_`.
+///
+/// The returned error's `input` is empty, as the input cannot be copied in a
+/// `const` context. Use [`validate_identifier_kind`] to learn where the error
+/// occurred without allocating.
 pub const fn validate_identifier(name: &str) -> Result<(), InvalidIdentifier> {
+    match validate_identifier_kind(name) {
+        Ok(()) => Ok(()),
+        Err(kind) => Err(InvalidIdentifier {
+            input: String::new(),
+            kind,
+        }),
+    }
+}
+
+/// Validates `name` like [`validate_identifier`], returning the reason and
+/// byte offset of the first problem found.
+pub const fn validate_identifier_kind(name: &str) -> Result<(), InvalidIdentifierKind> {
+    if name.is_empty() {
+        return Err(InvalidIdentifierKind::Empty { offset: 0 });
+    }
+    let bytes = name.as_bytes();
     let mut index = 0;
-    while index < name.len() {
-        let byte = name.as_bytes()[index];
+    while index < bytes.len() {
+        let byte = bytes[index];
         if byte.is_ascii_alphanumeric() || byte == b'_' {
             index += 1;
         } else {
-            return Err(InvalidIdentifier);
+            return Err(InvalidIdentifierKind::InvalidCharacter {
+                offset: index,
+                character: decode_char(bytes, index),
+            });
         }
     }
     Ok(())
 }
 
+/// Decodes the character starting at `index` in `bytes`, which must contain
+/// valid UTF-8.
+const fn decode_char(bytes: &[u8], index: usize) -> char {
+    let first = bytes[index] as u32;
+    let (width, mut code) = if first < 0x80 {
+        (1, first)
+    } else if first & 0xE0 == 0xC0 {
+        (2, first & 0x1F)
+    } else if first & 0xF0 == 0xE0 {
+        (3, first & 0x0F)
+    } else {
+        (4, first & 0x07)
+    };
+    let mut offset = 1;
+    while offset < width {
+        code = (code << 6) | (bytes[index + offset] as u32 & 0x3F);
+        offset += 1;
+    }
+    match char::from_u32(code) {
+        Some(ch) => ch,
+        None => char::REPLACEMENT_CHARACTER,
+    }
+}
+
+/// Validates `name` as an identifier and converts it to `snake_case` using
+/// [`to_snake_case`].
 pub fn pascal_case_to_snake_case(name: String) -> Result<String, InvalidIdentifier> {
-    let mut bytes = name.into_bytes();
-    let mut index = 0;
-    let mut previous_was_upper = false;
-    while let Some(ch) = bytes.get(index).copied() {
-        if !ch.is_ascii_alphanumeric() && ch != b'_' {
-            return Err(InvalidIdentifier);
-        }
-        let is_upper = ch.is_ascii_uppercase();
-        let next_is_upper = bytes
-            .get(index + 1)
-            .map_or(false, |c| c.is_ascii_uppercase());
-        if is_upper {
-            if previous_was_upper && !next_is_upper {
-                bytes.insert(index, b'_');
-                index += 1;
+    if let Err(kind) = validate_identifier_kind(&name) {
+        return Err(InvalidIdentifier::new(name, kind));
+    }
+    let snake = to_snake_case(&name);
+    if snake.is_empty() {
+        // A name made entirely of underscores contains no words.
+        return Err(InvalidIdentifier::new(
+            name,
+            InvalidIdentifierKind::Empty { offset: 0 },
+        ));
+    }
+    Ok(snake)
+}
+
+/// Splits `name` into words. Words are separated by `_`, `-`, and changes in
+/// case. A run of uppercase letters is treated as a single word, e.g.,
+/// `HTTPServer` is split into `HTTP` and `Server`.
+fn words(name: &str) -> Vec<&str> {
+    let bytes = name.as_bytes();
+    let mut words = Vec::new();
+    let mut start = 0;
+    for (index, byte) in bytes.iter().enumerate() {
+        if *byte == b'_' || *byte == b'-' {
+            if start < index {
+                words.push(&name[start..index]);
             }
-            bytes[index] = ch.to_ascii_lowercase();
-            index += 1;
-        } else {
-            index += 1;
-            if next_is_upper {
-                bytes.insert(index, b'_');
-                index += 1;
+            start = index + 1;
+        } else if byte.is_ascii_uppercase() && index > start {
+            let previous_is_upper = bytes[index - 1].is_ascii_uppercase();
+            let next_is_lower = bytes.get(index + 1).map_or(false, u8::is_ascii_lowercase);
+            if !previous_is_upper || next_is_lower {
+                words.push(&name[start..index]);
+                start = index;
             }
         }
-        previous_was_upper = is_upper;
     }
-    Ok(String::from_utf8(bytes).expect("invalid unicode is rejected"))
+    if start < name.len() {
+        words.push(&name[start..]);
+    }
+    words
+}
+
+fn join_words(
+    name: &str,
+    separator: &str,
+    mut transform: impl FnMut(usize, &str) -> String,
+) -> String {
+    words(name)
+        .into_iter()
+        .enumerate()
+        .map(|(index, word)| transform(index, word))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect()
+    })
+}
+
+/// Converts `name` from any supported case convention to `snake_case`.
+pub fn to_snake_case(name: &str) -> String {
+    join_words(name, "_", |_, word| word.to_lowercase())
+}
+
+/// Converts `name` from any supported case convention to `kebab-case`.
+pub fn to_kebab_case(name: &str) -> String {
+    join_words(name, "-", |_, word| word.to_lowercase())
+}
+
+/// Converts `name` from any supported case convention to `camelCase`.
+pub fn to_camel_case(name: &str) -> String {
+    join_words(name, "", |index, word| {
+        if index == 0 {
+            word.to_lowercase()
+        } else {
+            capitalize(word)
+        }
+    })
+}
+
+/// Converts `name` from any supported case convention to `PascalCase`.
+pub fn to_pascal_case(name: &str) -> String {
+    join_words(name, "", |_, word| capitalize(word))
+}
+
+/// An error returned when validating an identifier.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct InvalidIdentifier {
+    /// The input that was being validated.
+    pub input: String,
+    /// The reason the input is invalid.
+    pub kind: InvalidIdentifierKind,
 }
 
-#[derive(Debug)]
-pub struct InvalidIdentifier;
+impl InvalidIdentifier {
+    /// Returns a new error for `input`.
+    pub fn new(input: impl Into<String>, kind: InvalidIdentifierKind) -> Self {
+        Self {
+            input: input.into(),
+            kind,
+        }
+    }
+}
 
 impl Display for InvalidIdentifier {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("invalid character found in identifier")
+        match self.kind {
+            InvalidIdentifierKind::Empty { .. } if self.input.is_empty() => {
+                f.write_str("identifier is empty")
+            }
+            InvalidIdentifierKind::Empty { offset } => {
+                write!(f, "empty identifier at byte {offset} in {:?}", self.input)
+            }
+            InvalidIdentifierKind::InvalidCharacter { offset, character }
+                if self.input.is_empty() =>
+            {
+                write!(
+                    f,
+                    "invalid character {character:?} at byte {offset} in identifier"
+                )
+            }
+            InvalidIdentifierKind::InvalidCharacter { offset, character } => write!(
+                f,
+                "invalid character {character:?} at byte {offset} in identifier {:?}",
+                self.input
+            ),
+        }
     }
 }
 
 impl Error for InvalidIdentifier {}
 
+/// The reason an identifier is invalid.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum InvalidIdentifierKind {
+    /// The identifier contained no characters.
+    Empty {
+        /// The byte offset within the input where the identifier was
+        /// expected.
+        offset: usize,
+    },
+    /// The identifier contained a character other than `a-z`, `A-Z`, `0-9`,
+    /// or `_`.
+    InvalidCharacter {
+        /// The byte offset of the character within the input.
+        offset: usize,
+        /// The invalid character.
+        character: char,
+    },
+}
+
+#[test]
+fn case_conversions() {
+    for name in ["padding_left", "padding-left", "paddingLeft", "PaddingLeft"] {
+        assert_eq!(to_snake_case(name), "padding_left");
+        assert_eq!(to_kebab_case(name), "padding-left");
+        assert_eq!(to_camel_case(name), "paddingLeft");
+        assert_eq!(to_pascal_case(name), "PaddingLeft");
+    }
+    assert_eq!(to_snake_case("aFFITest"), "a_ffi_test");
+    assert_eq!(to_kebab_case("HTTPServer2"), "http-server2");
+    assert_eq!(
+        to_camel_case("__leading__and_trailing__"),
+        "leadingAndTrailing"
+    );
+    assert_eq!(to_pascal_case(""), "");
+}
+
+#[test]
+fn identifier_validation() {
+    const VALID: Result<(), InvalidIdentifier> = validate_identifier("snake_case_1");
+    assert_eq!(VALID, Ok(()));
+    assert_eq!(
+        validate_identifier("kebab-case").unwrap_err().to_string(),
+        "invalid character '-' at byte 5 in identifier"
+    );
+    assert_eq!(validate_identifier_kind("snake_case_1"), Ok(()));
+    assert_eq!(
+        validate_identifier_kind(""),
+        Err(InvalidIdentifierKind::Empty { offset: 0 })
+    );
+    assert_eq!(
+        validate_identifier_kind("kebab-case"),
+        Err(InvalidIdentifierKind::InvalidCharacter {
+            offset: 5,
+            character: '-'
+        })
+    );
+    assert_eq!(
+        validate_identifier_kind("naïve"),
+        Err(InvalidIdentifierKind::InvalidCharacter {
+            offset: 2,
+            character: 'ï'
+        })
+    );
+    assert_eq!(
+        validate_identifier_kind("a🎨"),
+        Err(InvalidIdentifierKind::InvalidCharacter {
+            offset: 1,
+            character: '🎨'
+        })
+    );
+    let error = pascal_case_to_snake_case(String::from("Bad-Name")).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "invalid character '-' at byte 3 in identifier \"Bad-Name\""
+    );
+}
+
 #[test]
 fn pascal_conversion_tests() {
     assert_eq!(
@@ -70,4 +295,9 @@ fn pascal_conversion_tests() {
         pascal_case_to_snake_case(String::from("TestTest")).unwrap(),
         "test_test"
     );
+    assert_eq!(
+        pascal_case_to_snake_case(String::from("HTTPServer2")).unwrap(),
+        to_snake_case("HTTPServer2")
+    );
+    assert!(pascal_case_to_snake_case(String::from("__")).is_err());
 }